@@ -1,26 +1,117 @@
 extern crate stava;
 #[macro_use]
 extern crate clap;
-#[macro_use]
-extern crate include_dir;
 
-use clap::{Arg, Command};
-use include_dir::Dir;
-use stava::{Stava, StavaResult};
+use clap::{Arg, ArgMatches, Command};
+use regex::Regex;
+use serde::Serialize;
+use stava::config::{Config, OutputFormat};
+use stava::dictionary::{Dictionary, DictionarySpec};
+use stava::diff::Diff;
+use stava::error::Result;
+use stava::eval::{Evaluation, TestSet};
+use stava::explain::Explanation;
+use stava::export::{ExportFormat, ExportOptions, SortOrder};
+use stava::forbidden::ForbiddenWords;
+use stava::frequency::{FrequencyList, FrequencyOptions};
+use stava::hunspell::Hunspell;
+use stava::import::{ImportFormat, WordList};
+use stava::merge::MergeStrategy;
+use stava::personal::PersonalDictionary;
+use stava::replacements::Replacements;
+use stava::stats::{Lookup, Stats};
+use stava::tokenizer::{Tokenizer, TokenizerKind};
+use stava::walk::{self, FileFilter};
+use stava::{Language, Misspelling, Ranking, Stava, StavaError, StavaResult};
 
-use std::collections::HashMap;
+use std::env;
 use std::ffi::OsStr;
-use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 
 const OPT_NAME_WORD: &str = "WORD";
 const OPT_NAME_FILES: &str = "FILES";
+const OPT_NAME_PATHS: &str = "PATHS";
+const OPT_NAME_LEARN: &str = "LEARN";
+const OPT_NAME_TEXT: &str = "TEXT";
+const OPT_NAME_INCLUDE: &str = "INCLUDE";
+const OPT_NAME_EXCLUDE: &str = "EXCLUDE";
+const OPT_NAME_DICT_DIR: &str = "DICT_DIR";
+const OPT_NAME_DICT_WORDS: &str = "WORDS";
+const OPT_NAME_DICT_FILES: &str = "DICT_FILES";
+const OPT_NAME_OLD: &str = "OLD";
+const OPT_NAME_NEW: &str = "NEW";
+const OPT_NAME_MODELS: &str = "MODELS";
+const OPT_NAME_MODEL: &str = "MODEL";
+const OPT_NAME_FACTOR: &str = "FACTOR";
+const OPT_NAME_STRATEGY: &str = "STRATEGY";
+const OPT_NAME_WEIGHT: &str = "WEIGHT";
+const OPT_NAME_LANGUAGE: &str = "LANGUAGE";
+const OPT_NAME_MAX_DISTANCE: &str = "MAX_DISTANCE";
+const OPT_NAME_MIN_CONFIDENCE: &str = "MIN_CONFIDENCE";
+const OPT_NAME_RANKING: &str = "RANKING";
+const OPT_NAME_EDIT_PENALTY: &str = "EDIT_PENALTY";
+const OPT_NAME_TOKENIZER: &str = "TOKENIZER";
+const OPT_NAME_TOKEN_REGEX: &str = "TOKEN_REGEX";
+const OPT_NAME_FORMAT: &str = "FORMAT";
+const OPT_NAME_EXPORT_FORMAT: &str = "EXPORT_FORMAT";
+const OPT_NAME_IMPORT_FORMAT: &str = "IMPORT_FORMAT";
+const OPT_NAME_REPLACEMENTS: &str = "REPLACEMENTS";
+const OPT_NAME_FORBIDDEN: &str = "FORBIDDEN";
+const OPT_NAME_FREQUENCY_LISTS: &str = "FREQUENCY_LISTS";
+const OPT_NAME_HUNSPELL: &str = "HUNSPELL";
+const OPT_NAME_MIN_FREQUENCY: &str = "MIN_FREQUENCY";
+const OPT_NAME_SORT: &str = "SORT";
+const OPT_NAME_TOP: &str = "TOP";
+const OPT_NAME_TESTSET: &str = "TESTSET";
+const OPT_NAME_WORST: &str = "WORST";
+const OPT_NAME_MIN_COUNT: &str = "MIN_COUNT";
+const OPT_NAME_COUNT_SCALE: &str = "COUNT_SCALE";
 const FLAG_INC_DEFAULT_WORDS: &str = "flag_inc_default_words";
 const FLAG_RETURN_EXIT_CODE: &str = "flag_return_exit_code";
 const FLAG_ONLY_EXIT_CODE: &str = "flag_only_exit_code";
+const FLAG_EXPLAIN: &str = "flag_explain";
+const FLAG_IGNORE_LIST: &str = "flag_ignore_list";
+const FLAG_BAD_WORDS: &str = "flag_bad_words";
+
+const SUBCOMMAND_CHECK: &str = "check";
+const SUBCOMMAND_DICT: &str = "dict";
+const SUBCOMMAND_SEGMENT: &str = "segment";
+const SUBCOMMAND_EXPORT: &str = "export";
+const SUBCOMMAND_STATS: &str = "stats";
+const SUBCOMMAND_LOOKUP: &str = "lookup";
+const SUBCOMMAND_EVAL: &str = "eval";
+const SUBCOMMAND_DICT_ADD: &str = "add";
+const SUBCOMMAND_DICT_REMOVE: &str = "remove";
+const SUBCOMMAND_DICT_LIST: &str = "list";
+const SUBCOMMAND_DICT_IMPORT: &str = "import";
+const SUBCOMMAND_DICT_DIFF: &str = "diff";
+const SUBCOMMAND_DICT_MERGE: &str = "merge";
+const SUBCOMMAND_DICT_FORGET: &str = "forget";
+const SUBCOMMAND_DICT_UNLEARN: &str = "unlearn";
+const SUBCOMMAND_DICT_DECAY: &str = "decay";
+
+// Exit code when the word could not be corrected or the files could not be checked
+const EXIT_CODE_ERROR: i32 = 2;
+// Files with these extensions are read as saved word counts instead of learning their words
+const MODEL_EXTENSIONS: [&str; 2] = ["stava", "tsv"];
+
+// Files to learn words from, with the dictionary their words are reported as coming from
+type LearnFiles = Vec<(PathBuf, Option<Dictionary>)>;
+
+#[derive(Serialize)]
+struct FileMisspelling<'a> {
+    path: String,
+    #[serde(flatten)]
+    misspelling: &'a Misspelling,
+}
 
-const ASSETS_DIR: Dir = include_dir!("src/assets");
+#[derive(Serialize)]
+struct Segmentation<'a> {
+    text: &'a str,
+    words: &'a [String],
+}
 
 fn main() {
     let opt_word = Arg::new(OPT_NAME_WORD)
@@ -29,17 +120,19 @@ fn main() {
         .index(1);
 
     let opt_files = Arg::new(OPT_NAME_FILES)
-        .help("Files to learn words from")
+        .help("Files, directories or glob patterns to learn words from, as `path[:weight[:priority]]`")
         .takes_value(true)
         .multiple_values(true)
         .required(false)
-        .validator_os(exists_on_filesystem)
+        .allow_invalid_utf8(true)
+        .validator_os(dictionary_exists)
         .index(2);
 
     let flag_inc_default_words = Arg::new(FLAG_INC_DEFAULT_WORDS)
         .help("Include default set of words (default: false)")
         .short('d')
-        .long("default");
+        .long("default")
+        .global(true);
 
     let flag_return_exit_code = Arg::new(FLAG_RETURN_EXIT_CODE)
         .help("Exit with 1 if word is not spelled correctly, otherwise 0 (default: false)")
@@ -51,50 +144,1237 @@ fn main() {
         .short('o')
         .long("exit-code-only");
 
+    let flag_explain = Arg::new(FLAG_EXPLAIN)
+        .help("Show the edits, candidates and rule that chose the correction (default: false)")
+        .long("explain")
+        .conflicts_with(FLAG_ONLY_EXIT_CODE);
+
+    let opt_include = Arg::new(OPT_NAME_INCLUDE)
+        .help("Only use files matching this glob pattern when walking directories")
+        .long("include")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .global(true);
+
+    let opt_exclude = Arg::new(OPT_NAME_EXCLUDE)
+        .help("Skip files matching this glob pattern when walking directories")
+        .long("exclude")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .global(true);
+
+    let opt_dict_dir = Arg::new(OPT_NAME_DICT_DIR)
+        .help("Directory of the personal dictionary (default: $XDG_DATA_HOME/stava)")
+        .long("dict-dir")
+        .takes_value(true)
+        .allow_invalid_utf8(true)
+        .global(true);
+
+    let opt_language = Arg::new(OPT_NAME_LANGUAGE)
+        .help("Language of the words (default: en)")
+        .short('l')
+        .long("language")
+        .takes_value(true)
+        .validator(|value| value.parse::<Language>())
+        .global(true);
+
+    let opt_max_distance = Arg::new(OPT_NAME_MAX_DISTANCE)
+        .help("Maximum number of edits between a word and its correction (default: 2)")
+        .long("max-distance")
+        .takes_value(true)
+        .validator(|value| value.parse::<usize>())
+        .global(true);
+
+    let opt_min_confidence = Arg::new(OPT_NAME_MIN_CONFIDENCE)
+        .help("Words are not corrected when the correction is less confident than this, from 0 to 1 (default: 0)")
+        .long("min-confidence")
+        .takes_value(true)
+        .validator(|value| match value.parse::<f64>() {
+            Ok(confidence) if (0.0..=1.0).contains(&confidence) => Ok(()),
+            _ => Err(format!("Expected a number between 0 and 1 [{}]", value)),
+        })
+        .global(true);
+
+    let opt_ranking = Arg::new(OPT_NAME_RANKING)
+        .help("How the correction is chosen: norvig (the most frequent of the candidates with the fewest edits) or combined (all candidates, with a penalty for each edit) (default: norvig)")
+        .long("ranking")
+        .takes_value(true)
+        .possible_values(["norvig", "combined"])
+        .global(true);
+
+    let opt_edit_penalty = Arg::new(OPT_NAME_EDIT_PENALTY)
+        .help("How many times as frequent a candidate with one more edit must be to win, switches on the combined ranking (default: 100)")
+        .long("edit-penalty")
+        .takes_value(true)
+        .validator(|value| match value.parse::<f64>() {
+            Ok(penalty) if penalty >= 1.0 && penalty.is_finite() => Ok(()),
+            _ => Err(format!("Expected a number of at least 1 [{}]", value)),
+        })
+        .global(true);
+
+    let opt_replacements = Arg::new(OPT_NAME_REPLACEMENTS)
+        .help("File of `wrong -> right` replacements that are always made, one per line")
+        .long("replacements")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .allow_invalid_utf8(true)
+        .global(true);
+
+    let opt_forbidden = Arg::new(OPT_NAME_FORBIDDEN)
+        .help("File of words that are never accepted, one `word` or `word -> preferred` per line")
+        .long("forbidden")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .allow_invalid_utf8(true)
+        .global(true);
+
+    let opt_frequency_lists = Arg::new(OPT_NAME_FREQUENCY_LISTS)
+        .help("File of `word<TAB>count` lines to learn words from, as `path[:weight[:priority]]`")
+        .long("frequency-list")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .validator_os(dictionary_exists)
+        .global(true);
+
+    let opt_hunspell = Arg::new(OPT_NAME_HUNSPELL)
+        .help("Hunspell .dic file, with its .aff file next to it, as `path[:weight[:priority]]`")
+        .long("hunspell")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .validator_os(dictionary_exists)
+        .global(true);
+
+    let opt_min_count = Arg::new(OPT_NAME_MIN_COUNT)
+        .help("Skip words counted fewer times than this in frequency lists (default: 1)")
+        .long("min-count")
+        .takes_value(true)
+        .validator(|value| value.parse::<u64>())
+        .global(true);
+
+    let opt_count_scale = Arg::new(OPT_NAME_COUNT_SCALE)
+        .help("Multiply the counts in frequency lists by this (default: 1.0)")
+        .long("count-scale")
+        .takes_value(true)
+        .validator(is_positive_number)
+        .global(true);
+
+    let opt_tokenizer = Arg::new(OPT_NAME_TOKENIZER)
+        .help("How text is split into words (default: ascii)")
+        .long("tokenizer")
+        .takes_value(true)
+        .possible_values(["ascii", "unicode"])
+        .global(true);
+
+    let opt_token_regex = Arg::new(OPT_NAME_TOKEN_REGEX)
+        .help("Regex matching the words in lowercased text, used instead of --tokenizer")
+        .long("token-regex")
+        .takes_value(true)
+        .conflicts_with(OPT_NAME_TOKENIZER)
+        .global(true);
+
+    let opt_format = Arg::new(OPT_NAME_FORMAT)
+        .help("Output format (default: plain)")
+        .short('f')
+        .long("format")
+        .takes_value(true)
+        .possible_values(["plain", "json"])
+        .global(true);
+
     let matches = Command::new("stava")
         .version(crate_version!())
         .author(crate_authors!())
         .about(crate_description!())
+        .subcommand_negates_reqs(true)
         .arg(opt_word)
         .arg(opt_files)
         .arg(flag_inc_default_words)
         .arg(flag_return_exit_code)
+        .arg(flag_explain)
         .arg(flag_only_exit_code)
+        .arg(opt_include)
+        .arg(opt_exclude)
+        .arg(opt_dict_dir)
+        .arg(opt_language)
+        .arg(opt_max_distance)
+        .arg(opt_min_confidence)
+        .arg(opt_ranking)
+        .arg(opt_edit_penalty)
+        .arg(opt_replacements)
+        .arg(opt_forbidden)
+        .arg(opt_frequency_lists)
+        .arg(opt_hunspell)
+        .arg(opt_min_count)
+        .arg(opt_count_scale)
+        .arg(opt_tokenizer)
+        .arg(opt_token_regex)
+        .arg(opt_format)
+        .subcommand(check_command())
+        .subcommand(dict_command())
+        .subcommand(segment_command())
+        .subcommand(export_command())
+        .subcommand(stats_command())
+        .subcommand(lookup_command())
+        .subcommand(eval_command())
         .get_matches();
 
-    let mut stava = Stava {
-        words_w_count: HashMap::new(),
-    };
+    let dict_dir = matches
+        .value_of_os(OPT_NAME_DICT_DIR)
+        .map(PathBuf::from)
+        .or_else(PersonalDictionary::default_dir);
 
-    if let Some(files) = matches.values_of(OPT_NAME_FILES) {
-        if matches.is_present(FLAG_INC_DEFAULT_WORDS) {
-            stava.learn(get_default_words());
+    let result = match matches.subcommand() {
+        Some((SUBCOMMAND_CHECK, check_matches)) => {
+            load_config().and_then(|config| run_check(check_matches, &config, dict_dir))
+        }
+        Some((SUBCOMMAND_DICT, dict_matches)) => match dict_matches.subcommand() {
+            Some((SUBCOMMAND_DICT_DIFF, diff_matches)) => {
+                load_config().and_then(|config| run_dict_diff(diff_matches, &config))
+            }
+            Some((SUBCOMMAND_DICT_MERGE, merge_matches)) => {
+                load_config().and_then(|config| run_dict_merge(merge_matches, &config))
+            }
+            Some((SUBCOMMAND_DICT_FORGET, forget_matches)) => {
+                load_config().and_then(|config| run_dict_forget(forget_matches, &config))
+            }
+            Some((SUBCOMMAND_DICT_UNLEARN, unlearn_matches)) => {
+                load_config().and_then(|config| run_dict_unlearn(unlearn_matches, &config))
+            }
+            Some((SUBCOMMAND_DICT_DECAY, decay_matches)) => {
+                load_config().and_then(|config| run_dict_decay(decay_matches, &config))
+            }
+            _ => dict_dir
+                .ok_or(StavaError::MissingDataDir)
+                .and_then(|dict_dir| run_dict(dict_matches, &dict_dir)),
+        },
+        Some((SUBCOMMAND_SEGMENT, segment_matches)) => {
+            load_config().and_then(|config| run_segment(segment_matches, &config, dict_dir))
+        }
+        Some((SUBCOMMAND_EXPORT, export_matches)) => {
+            load_config().and_then(|config| run_export(export_matches, &config, dict_dir))
+        }
+        Some((SUBCOMMAND_STATS, stats_matches)) => {
+            load_config().and_then(|config| run_stats(stats_matches, &config, dict_dir))
+        }
+        Some((SUBCOMMAND_LOOKUP, lookup_matches)) => {
+            load_config().and_then(|config| run_lookup(lookup_matches, &config, dict_dir))
+        }
+        Some((SUBCOMMAND_EVAL, eval_matches)) => {
+            load_config().and_then(|config| run_eval(eval_matches, &config, dict_dir))
         }
+        _ => load_config().and_then(|config| run_correct(&matches, &config, dict_dir)),
+    };
 
-        let paths: Vec<&Path> = files.map(Path::new).collect::<Vec<&Path>>();
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        exit(EXIT_CODE_ERROR)
+    }
+}
+
+fn run_correct(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let files = match matches.values_of_os(OPT_NAME_FILES) {
+        Some(files) => Some(find_dictionary_files(files, &file_filter(matches, config))?),
+        None => None,
+    };
+    let stava = build_stava(matches, config, files, dict_dir)?;
 
-        for file in paths {
-            let words = fs::read_to_string(file)
-                .unwrap_or_else(|_| panic!("Could not read the file: {}", file.display()));
-            stava.learn(words.as_str());
+    let word = matches.value_of(OPT_NAME_WORD).unwrap();
+    let is_ignored = is_ignored(word, &ignore_patterns(config)?);
+    if matches.is_present(FLAG_EXPLAIN) && !is_ignored {
+        let explanation = stava.explain(word)?;
+        print_explanation(&explanation, output_format(matches, config));
+        if matches.is_present(FLAG_RETURN_EXIT_CODE) {
+            exit_with_code(explanation.result)
         }
-    } else {
-        // No files provided by user - use default word file
-        stava.learn(get_default_words());
+        return Ok(());
     }
 
-    let word = matches.value_of(OPT_NAME_WORD).unwrap();
-    let result = stava.correct(word);
+    let result = if is_ignored {
+        StavaResult {
+            word: word.to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 1.0,
+        }
+    } else {
+        stava.correct(word)?
+    };
 
     if matches.is_present(FLAG_ONLY_EXIT_CODE) {
         exit_with_code(result)
     } else {
-        println!("{}", result.word);
+        print_result(&result, output_format(matches, config));
 
         if matches.is_present(FLAG_RETURN_EXIT_CODE) {
             exit_with_code(result)
         }
     }
+
+    Ok(())
+}
+
+fn check_command() -> Command<'static> {
+    let opt_paths = Arg::new(OPT_NAME_PATHS)
+        .help("Files, directories or glob patterns to check (default: .)")
+        .takes_value(true)
+        .multiple_values(true)
+        .allow_invalid_utf8(true)
+        .validator_os(exists_on_filesystem);
+
+    Command::new(SUBCOMMAND_CHECK)
+        .about("Check the spelling of the words in files, exits with 1 if any is misspelled")
+        .arg(opt_paths)
+        .arg(learn_arg())
+}
+
+fn learn_arg() -> Arg<'static> {
+    Arg::new(OPT_NAME_LEARN)
+        .help("Files, directories or glob patterns to learn words from, as `path[:weight[:priority]]`")
+        .long("learn")
+        .takes_value(true)
+        .multiple_occurrences(true)
+        .allow_invalid_utf8(true)
+        .validator_os(dictionary_exists)
+}
+
+fn learn_files(matches: &ArgMatches, filter: &FileFilter) -> Result<Option<LearnFiles>> {
+    match matches.values_of_os(OPT_NAME_LEARN) {
+        Some(files) => Ok(Some(find_dictionary_files(files, filter)?)),
+        None => Ok(None),
+    }
+}
+
+fn run_check(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let filter = file_filter(matches, config);
+    let learn_files = learn_files(matches, &filter)?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    let files = match matches.values_of_os(OPT_NAME_PATHS) {
+        Some(paths) => find_files(paths, &filter)?,
+        None => find_files([OsStr::new(".")].into_iter(), &filter)?,
+    };
+    let ignore_patterns = ignore_patterns(config)?;
+    let format = output_format(matches, config);
+
+    let mut found_misspelling = false;
+    for file in files {
+        let text = match walk::read_text_file(&file)? {
+            Some(text) => text,
+            None => continue,
+        };
+
+        for misspelling in stava.check(&text) {
+            if !is_ignored(&misspelling.word, &ignore_patterns) {
+                found_misspelling = true;
+                print_misspelling(&file, &misspelling, format);
+            }
+        }
+    }
+
+    exit(if found_misspelling { 1 } else { 0 })
+}
+
+fn segment_command() -> Command<'static> {
+    let opt_text = Arg::new(OPT_NAME_TEXT)
+        .help("Text with words that are run together or split, e.g. \"thisis\" or \"spel ling\"")
+        .takes_value(true)
+        .multiple_values(true)
+        .required(true);
+
+    Command::new(SUBCOMMAND_SEGMENT)
+        .about("Split run-together words and join split words")
+        .arg(opt_text)
+        .arg(learn_arg())
+}
+
+fn run_segment(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let learn_files = learn_files(matches, &file_filter(matches, config))?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    let text = matches
+        .values_of(OPT_NAME_TEXT)
+        .unwrap()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let words = stava.segment(&text);
+
+    match output_format(matches, config) {
+        OutputFormat::Plain => println!("{}", words.join(" ")),
+        OutputFormat::Json => {
+            let segmentation = Segmentation {
+                text: &text,
+                words: &words,
+            };
+            println!("{}", serde_json::to_string(&segmentation).unwrap())
+        }
+    }
+
+    Ok(())
+}
+
+fn export_command() -> Command<'static> {
+    let opt_export_format = Arg::new(OPT_NAME_EXPORT_FORMAT)
+        .help("Format of the exported words (default: tsv)")
+        .long("to")
+        .takes_value(true)
+        .possible_values(["hunspell", "aspell-pws", "vim-add", "tsv"]);
+
+    let opt_min_frequency = Arg::new(OPT_NAME_MIN_FREQUENCY)
+        .help("Skip words learned fewer times than this (default: 1)")
+        .long("min-frequency")
+        .takes_value(true)
+        .validator(|value| value.parse::<u32>());
+
+    let opt_sort = Arg::new(OPT_NAME_SORT)
+        .help("Order of the exported words (default: frequency)")
+        .long("sort")
+        .takes_value(true)
+        .possible_values(["frequency", "alphabet"]);
+
+    Command::new(SUBCOMMAND_EXPORT)
+        .about("Write the learned words to stdout in the format of another spell checker")
+        .arg(opt_export_format)
+        .arg(opt_min_frequency)
+        .arg(opt_sort)
+        .arg(learn_arg())
+}
+
+fn run_export(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let learn_files = learn_files(matches, &file_filter(matches, config))?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    // The output format of the other commands does not apply to the exported words
+    if matches.is_present(OPT_NAME_FORMAT) {
+        return Err(StavaError::invalid_setting(
+            "format",
+            "The exported words are written in the format given by --to",
+        ));
+    }
+    let format = matches
+        .value_of_t(OPT_NAME_EXPORT_FORMAT)
+        .unwrap_or(ExportFormat::Tsv);
+    let default_options = ExportOptions::default();
+    let options = ExportOptions {
+        min_count: matches
+            .value_of_t(OPT_NAME_MIN_FREQUENCY)
+            .unwrap_or(default_options.min_count),
+        sort: matches
+            .value_of_t::<SortOrder>(OPT_NAME_SORT)
+            .unwrap_or(default_options.sort),
+    };
+
+    let stdout = io::stdout();
+    stava
+        .export(io::BufWriter::new(stdout.lock()), format, &options)
+        .map_err(|err| StavaError::Io {
+            path: PathBuf::from("<stdout>"),
+            source: err,
+        })
+}
+
+fn stats_command() -> Command<'static> {
+    let opt_top = Arg::new(OPT_NAME_TOP)
+        .help("Number of the most frequent words to show (default: 10)")
+        .long("top")
+        .takes_value(true)
+        .validator(|value| value.parse::<usize>());
+
+    Command::new(SUBCOMMAND_STATS)
+        .about("Show statistics of the learned words")
+        .arg(opt_top)
+        .arg(learn_arg())
+}
+
+fn run_stats(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let learn_files = learn_files(matches, &file_filter(matches, config))?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    let stats = stava.stats(matches.value_of_t(OPT_NAME_TOP).unwrap_or(10));
+    match output_format(matches, config) {
+        OutputFormat::Plain => print_stats(&stats),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&stats).unwrap()),
+    }
+
+    Ok(())
+}
+
+fn print_stats(stats: &Stats) {
+    println!("Total words: {}", stats.total_count);
+    println!("Unique words: {}", stats.unique_words);
+    println!("Singleton ratio: {:.3}", stats.singleton_ratio);
+    println!("Missing letters: {}", stats.missing_letters.join(" "));
+    println!(
+        "Other characters: {}",
+        stats
+            .other_characters
+            .iter()
+            .map(char::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    println!("Top words:");
+    for (index, word_count) in stats.top_words.iter().enumerate() {
+        println!("  {}. {} {}", index + 1, word_count.word, word_count.count);
+    }
+    println!("Word lengths:");
+    for (length, words) in &stats.length_distribution {
+        println!("  {}: {}", length, words);
+    }
+}
+
+fn lookup_command() -> Command<'static> {
+    let opt_word = Arg::new(OPT_NAME_WORD)
+        .help("Word to look up")
+        .required(true);
+
+    Command::new(SUBCOMMAND_LOOKUP)
+        .about("Show the count and rank of a word, exits with 1 if it is not learned")
+        .arg(opt_word)
+        .arg(learn_arg())
+}
+
+fn run_lookup(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let learn_files = learn_files(matches, &file_filter(matches, config))?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    let lookup = stava.lookup(matches.value_of(OPT_NAME_WORD).unwrap());
+    match output_format(matches, config) {
+        OutputFormat::Plain => print_lookup(&lookup, stava.len()),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&lookup).unwrap()),
+    }
+
+    exit(if lookup.count.is_some() { 0 } else { 1 })
+}
+
+fn print_lookup(lookup: &Lookup, unique_words: usize) {
+    match (lookup.count, lookup.rank) {
+        (Some(count), Some(rank)) => {
+            print!(
+                "{}: count {}, rank {} of {}",
+                lookup.word, count, rank, unique_words
+            );
+            if let Some(dictionary) = &lookup.dictionary {
+                print!(", from {}", dictionary);
+            }
+            if lookup.forbidden {
+                print!(", forbidden");
+            }
+            println!();
+        }
+        _ => println!("{}: not learned", lookup.word),
+    }
+}
+
+fn eval_command() -> Command<'static> {
+    let opt_testset = Arg::new(OPT_NAME_TESTSET)
+        .help("Test set with a right spelling and its misspellings per line, as `right: wrong1 wrong2`")
+        .required(true)
+        .allow_invalid_utf8(true)
+        .validator_os(exists_on_filesystem);
+
+    let opt_worst = Arg::new(OPT_NAME_WORST)
+        .help("Number of the failures to show, those with the most frequent right spelling first (default: 10)")
+        .long("worst")
+        .takes_value(true)
+        .validator(|value| value.parse::<usize>());
+
+    Command::new(SUBCOMMAND_EVAL)
+        .about("Measure the accuracy of the corrections on a test set of misspellings")
+        .arg(opt_testset)
+        .arg(opt_worst)
+        .arg(learn_arg())
+}
+
+fn run_eval(matches: &ArgMatches, config: &Config, dict_dir: Option<PathBuf>) -> Result<()> {
+    let test_set = TestSet::load(Path::new(matches.value_of_os(OPT_NAME_TESTSET).unwrap()))?;
+    let learn_files = learn_files(matches, &file_filter(matches, config))?;
+    let stava = build_stava(matches, config, learn_files, dict_dir)?;
+
+    let evaluation = stava.evaluate(&test_set, matches.value_of_t(OPT_NAME_WORST).unwrap_or(10));
+    match output_format(matches, config) {
+        OutputFormat::Plain => print_evaluation(&evaluation),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&evaluation).unwrap()),
+    }
+
+    Ok(())
+}
+
+fn print_evaluation(evaluation: &Evaluation) {
+    println!("Words: {}", evaluation.words);
+    println!(
+        "Accuracy: {:.1}% ({} correct)",
+        evaluation.accuracy * 100.0,
+        evaluation.correct
+    );
+    println!(
+        "Unknown: {:.1}% ({} not learned)",
+        evaluation.unknown_rate * 100.0,
+        evaluation.unknown
+    );
+    println!("Words per second: {:.0}", evaluation.words_per_second);
+    println!("Worst failures:");
+    for failure in &evaluation.failures {
+        let count = |count: Option<u32>| count.map_or("unknown".to_string(), |c| c.to_string());
+        match &failure.suggestion {
+            Some(suggestion) => println!(
+                "  {} -> {} ({}), expected {} ({})",
+                failure.wrong,
+                suggestion,
+                count(failure.suggestion_count),
+                failure.right,
+                count(failure.right_count)
+            ),
+            None => println!(
+                "  {} is not a valid word, expected {} ({})",
+                failure.wrong,
+                failure.right,
+                count(failure.right_count)
+            ),
+        }
+    }
+}
+
+fn build_stava(
+    matches: &ArgMatches,
+    config: &Config,
+    files: Option<LearnFiles>,
+    dict_dir: Option<PathBuf>,
+) -> Result<Stava> {
+    let language: Language = matches
+        .value_of_t(OPT_NAME_LANGUAGE)
+        .ok()
+        .or(config.language)
+        .unwrap_or(Language::English);
+
+    let mut builder = Stava::builder()
+        .language(language)
+        .boxed_tokenizer(tokenizer(matches, config)?);
+
+    if let Some(max_distance) = matches
+        .value_of_t(OPT_NAME_MAX_DISTANCE)
+        .ok()
+        .or(config.max_distance)
+    {
+        builder = builder.max_distance(max_distance);
+    }
+    if let Some(min_confidence) = matches
+        .value_of_t(OPT_NAME_MIN_CONFIDENCE)
+        .ok()
+        .or(config.min_confidence)
+    {
+        builder = builder.min_confidence(min_confidence);
+    }
+    builder = builder.ranking(ranking(matches, config)?);
+
+    let files = match files {
+        Some(files) => files,
+        None => config_files(config)?,
+    };
+
+    let frequency_lists = specs_or(matches, OPT_NAME_FREQUENCY_LISTS, &config.frequency_lists)?;
+    let hunspell = specs_or(matches, OPT_NAME_HUNSPELL, &config.hunspell)?;
+
+    let default_words = (files.is_empty() && frequency_lists.is_empty() && hunspell.is_empty())
+        || matches.is_present(FLAG_INC_DEFAULT_WORDS)
+        || config.default_words.unwrap_or(false);
+    builder = builder.default_words(default_words);
+    for (file, dictionary) in files {
+        builder = match dictionary {
+            Some(dictionary) => builder.dictionary_path(file, dictionary),
+            None => builder.path(file),
+        };
+    }
+    let options = frequency_options(matches, config);
+    for spec in frequency_lists {
+        let list = FrequencyList::load(Path::new(&spec.path), &options)?;
+        builder = builder.dictionary_frequency_list(list, spec.dictionary());
+    }
+    for spec in hunspell {
+        let dictionary = Hunspell::load(Path::new(&spec.path))?;
+        builder = builder.dictionary_hunspell(dictionary, spec.dictionary());
+    }
+
+    for file in files_or(matches, OPT_NAME_REPLACEMENTS, &config.replacements) {
+        builder = builder.replacements(Replacements::load(&file)?);
+    }
+    for file in files_or(matches, OPT_NAME_FORBIDDEN, &config.forbidden) {
+        builder = builder.forbidden(ForbiddenWords::load(&file)?);
+    }
+
+    if let Some(dir) = dict_dir {
+        builder = builder.personal_dictionary(PersonalDictionary::load(&dir)?);
+    }
+
+    let stava = builder.build()?;
+    Ok(stava)
+}
+
+// An edit penalty switches on the combined ranking, unless another ranking is given
+fn ranking(matches: &ArgMatches, config: &Config) -> Result<Ranking> {
+    let ranking = matches.value_of_t(OPT_NAME_RANKING).ok().or(config.ranking);
+    let edit_penalty = matches
+        .value_of_t(OPT_NAME_EDIT_PENALTY)
+        .ok()
+        .or(config.edit_penalty);
+    match (ranking, edit_penalty) {
+        (None | Some(Ranking::Combined { .. }), Some(edit_penalty)) => {
+            Ok(Ranking::Combined { edit_penalty })
+        }
+        (Some(_), Some(_)) => Err(StavaError::invalid_setting(
+            "edit-penalty",
+            "Only the combined ranking has an edit penalty",
+        )),
+        (ranking, None) => Ok(ranking.unwrap_or_default()),
+    }
+}
+
+fn tokenizer(matches: &ArgMatches, config: &Config) -> Result<Box<dyn Tokenizer>> {
+    let kind = matches.value_of_t(OPT_NAME_TOKENIZER).ok();
+    let pattern = match kind {
+        Some(_) => matches.value_of(OPT_NAME_TOKEN_REGEX),
+        None => matches
+            .value_of(OPT_NAME_TOKEN_REGEX)
+            .or(config.token_regex.as_deref()),
+    };
+
+    match pattern {
+        Some(pattern) => {
+            let regex = Regex::new(pattern).map_err(|err| StavaError::InvalidPattern {
+                pattern: pattern.to_string(),
+                message: err.to_string(),
+            })?;
+            Ok(Box::new(regex))
+        }
+        None => Ok(kind
+            .or(config.tokenizer)
+            .unwrap_or(TokenizerKind::Ascii)
+            .tokenizer()),
+    }
+}
+
+// The files given by the option, or the files from the config if the option is not given
+fn files_or(matches: &ArgMatches, option: &str, config_files: &[PathBuf]) -> Vec<PathBuf> {
+    match matches.values_of_os(option) {
+        Some(files) => files.map(PathBuf::from).collect(),
+        None => config_files.to_vec(),
+    }
+}
+
+// The dictionaries given by the option, or the dictionaries from the config if it is not given
+fn specs_or(
+    matches: &ArgMatches,
+    option: &str,
+    config_specs: &[DictionarySpec],
+) -> Result<Vec<DictionarySpec>> {
+    match matches.values_of(option) {
+        Some(values) => values.map(parse_dictionary_spec).collect(),
+        None => Ok(config_specs.to_vec()),
+    }
+}
+
+fn load_config() -> Result<Config> {
+    let dir = env::current_dir().map_err(|err| StavaError::Io {
+        path: PathBuf::from("."),
+        source: err,
+    })?;
+    Ok(Config::discover(&dir)?.unwrap_or_default())
+}
+
+fn config_files(config: &Config) -> Result<LearnFiles> {
+    let matching_files = config.matching_files()?;
+    Ok(config
+        .dictionaries
+        .iter()
+        .map(|spec| (PathBuf::from(&spec.path), Some(spec.dictionary())))
+        .chain(matching_files.into_iter().map(|file| (file, None)))
+        .collect())
+}
+
+fn frequency_options(matches: &ArgMatches, config: &Config) -> FrequencyOptions {
+    let config_options = config.frequency_options();
+    FrequencyOptions {
+        min_count: matches
+            .value_of_t(OPT_NAME_MIN_COUNT)
+            .unwrap_or(config_options.min_count),
+        scale: matches
+            .value_of_t(OPT_NAME_COUNT_SCALE)
+            .unwrap_or(config_options.scale),
+    }
+}
+
+fn file_filter(matches: &ArgMatches, config: &Config) -> FileFilter {
+    let config_filter = config.file_filter();
+    let values = |name: &str| -> Option<Vec<String>> {
+        matches
+            .values_of(name)
+            .map(|values| values.map(str::to_string).collect())
+    };
+    FileFilter {
+        include: values(OPT_NAME_INCLUDE).unwrap_or(config_filter.include),
+        exclude: values(OPT_NAME_EXCLUDE).unwrap_or(config_filter.exclude),
+    }
+}
+
+// Expand glob patterns and walk directories
+fn find_files<'a>(
+    paths: impl Iterator<Item = &'a OsStr>,
+    filter: &FileFilter,
+) -> Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        match path.to_str() {
+            Some(pattern) => expanded.extend(walk::expand_glob(pattern)?),
+            None => expanded.push(PathBuf::from(path)),
+        }
+    }
+    walk::walk(&expanded, filter)
+}
+
+// Find the files of dictionary specs, e.g. `glossary.txt:10`. Each file is learned as part of the
+// dictionary named by the spec.
+fn find_dictionary_files<'a>(
+    values: impl Iterator<Item = &'a OsStr>,
+    filter: &FileFilter,
+) -> Result<LearnFiles> {
+    let mut result = Vec::new();
+    for value in values {
+        let spec = match value.to_str() {
+            Some(value) => parse_dictionary_spec(value)?,
+            None => {
+                let files = find_files([value].into_iter(), filter)?;
+                result.extend(files.into_iter().map(|file| (file, None)));
+                continue;
+            }
+        };
+        let dictionary = spec.dictionary();
+        let files = find_files([OsStr::new(&spec.path)].into_iter(), filter)?;
+        result.extend(
+            files
+                .into_iter()
+                .map(|file| (file, Some(dictionary.clone()))),
+        );
+    }
+    Ok(result)
+}
+
+fn parse_dictionary_spec(value: &str) -> Result<DictionarySpec> {
+    dictionary_spec(value).map_err(|message| StavaError::InvalidPattern {
+        pattern: value.to_string(),
+        message,
+    })
+}
+
+// Paths that exist are never split, so a file named like `words:2` is still found
+fn dictionary_spec(value: &str) -> Result<DictionarySpec, String> {
+    if Path::new(value).exists() {
+        Ok(DictionarySpec::new(value))
+    } else {
+        value.parse()
+    }
+}
+
+fn ignore_patterns(config: &Config) -> Result<Vec<Regex>> {
+    config
+        .ignore
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|err| StavaError::InvalidPattern {
+                pattern: pattern.to_string(),
+                message: err.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn is_ignored(word: &str, patterns: &[Regex]) -> bool {
+    patterns.iter().any(|pattern| pattern.is_match(word))
+}
+
+fn output_format(matches: &ArgMatches, config: &Config) -> OutputFormat {
+    matches
+        .value_of_t(OPT_NAME_FORMAT)
+        .ok()
+        .or(config.format)
+        .unwrap_or(OutputFormat::Plain)
+}
+
+fn print_result(result: &StavaResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Plain => println!("{}", result.word),
+        OutputFormat::Json => println!("{}", serde_json::to_string(result).unwrap()),
+    }
+}
+
+fn print_explanation(explanation: &Explanation, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string(explanation).unwrap());
+        return;
+    }
+
+    println!("{} -> {}", explanation.input, explanation.result.word);
+    println!("Rule: {}", explanation.rule);
+    println!("Confidence: {}", explanation.result.confidence);
+    println!("Distance: {}", explanation.distance);
+    if !explanation.edits.is_empty() {
+        println!("Edits:");
+        for edit in &explanation.edits {
+            println!("  {}", edit);
+        }
+    }
+    if !explanation.candidates.is_empty() {
+        println!("Candidates:");
+        for candidate in &explanation.candidates {
+            print!(
+                "  {}: count {}, distance {}",
+                candidate.word, candidate.count, candidate.distance
+            );
+            if candidate.priority != 0 {
+                print!(", priority {}", candidate.priority);
+            }
+            println!();
+        }
+    }
+}
+
+fn print_misspelling(file: &Path, misspelling: &Misspelling, format: OutputFormat) {
+    match format {
+        OutputFormat::Plain => match &misspelling.suggestion {
+            Some(suggestion) => println!(
+                "{}:{}:{}: {} -> {}",
+                file.display(),
+                misspelling.line,
+                misspelling.column,
+                misspelling.word,
+                suggestion
+            ),
+            None => println!(
+                "{}:{}:{}: {}",
+                file.display(),
+                misspelling.line,
+                misspelling.column,
+                misspelling.word
+            ),
+        },
+        OutputFormat::Json => {
+            let file_misspelling = FileMisspelling {
+                path: file.display().to_string(),
+                misspelling,
+            };
+            println!("{}", serde_json::to_string(&file_misspelling).unwrap())
+        }
+    }
+}
+
+fn dict_command() -> Command<'static> {
+    let opt_words = Arg::new(OPT_NAME_DICT_WORDS)
+        .help("Words to add, remove or forget")
+        .required(true)
+        .multiple_values(true);
+
+    let flag_ignore_list = Arg::new(FLAG_IGNORE_LIST)
+        .help("Use the ignore list, words that are never suggested (default: false)")
+        .short('i')
+        .long("ignore");
+
+    let flag_bad_words = Arg::new(FLAG_BAD_WORDS)
+        .help("Use the bad words, words that are never accepted or suggested (default: false)")
+        .short('b')
+        .long("bad")
+        .conflicts_with(FLAG_IGNORE_LIST);
+
+    let opt_files = Arg::new(OPT_NAME_DICT_FILES)
+        .help("Word lists to import, like ~/.aspell.en.pws or ~/.vim/spell/en.utf-8.add")
+        .required(true)
+        .multiple_values(true)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+
+    let opt_old = Arg::new(OPT_NAME_OLD)
+        .help("Saved word counts (.stava or .tsv) or a file to learn the old words from")
+        .required(true)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+    let opt_new = Arg::new(OPT_NAME_NEW)
+        .help("Saved word counts (.stava or .tsv) or a file to learn the new words from")
+        .required(true)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+    let opt_models = Arg::new(OPT_NAME_MODELS)
+        .help("Saved word counts (.stava or .tsv) or files to learn words from")
+        .required(true)
+        .multiple_values(true)
+        .min_values(2)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+
+    let opt_model = Arg::new(OPT_NAME_MODEL)
+        .help("Saved word counts (.stava or .tsv) or a file to learn words from")
+        .required(true)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+    let opt_unlearn_files = Arg::new(OPT_NAME_DICT_FILES)
+        .help("Files with the words to unlearn")
+        .required(true)
+        .multiple_values(true)
+        .allow_invalid_utf8(true)
+        .validator_os(is_file);
+
+    let opt_factor = Arg::new(OPT_NAME_FACTOR)
+        .help("Every count is multiplied by this, between 0 and 1")
+        .long("factor")
+        .required(true)
+        .takes_value(true)
+        .validator(|value| match value.parse::<f64>() {
+            Ok(factor) if (0.0..=1.0).contains(&factor) => Ok(()),
+            _ => Err(format!("Expected a number between 0 and 1 [{}]", value)),
+        });
+
+    let opt_strategy = Arg::new(OPT_NAME_STRATEGY)
+        .help("How the counts of a word in several files are combined (default: sum)")
+        .long("strategy")
+        .takes_value(true)
+        .possible_values(["sum", "max", "weighted"]);
+
+    let opt_weight = Arg::new(OPT_NAME_WEIGHT)
+        .help("Weight of the counts of each file merged into the ones before it with the weighted strategy (default: 0.5)")
+        .long("weight")
+        .takes_value(true)
+        .validator(|value| match value.parse::<f64>() {
+            Ok(weight) if (0.0..=1.0).contains(&weight) => Ok(()),
+            _ => Err(format!("Expected a number between 0 and 1 [{}]", value)),
+        });
+
+    let opt_import_format = Arg::new(OPT_NAME_IMPORT_FORMAT)
+        .help("Format of the word lists (default: .pws is aspell-pws, .add is vim-add, else hunspell)")
+        .long("from")
+        .takes_value(true)
+        .possible_values(["aspell-pws", "hunspell", "vim-add"]);
+
+    Command::new(SUBCOMMAND_DICT)
+        .about("Manage the personal dictionary")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_ADD)
+                .about("Add words to the personal dictionary")
+                .arg(opt_words.clone())
+                .arg(flag_ignore_list.clone())
+                .arg(flag_bad_words.clone()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_REMOVE)
+                .about("Remove words from the personal dictionary")
+                .arg(opt_words.clone())
+                .arg(flag_ignore_list.clone())
+                .arg(flag_bad_words.clone()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_LIST)
+                .about("List the words in the personal dictionary")
+                .arg(flag_ignore_list)
+                .arg(flag_bad_words),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_IMPORT)
+                .about("Import the words of aspell, Hunspell and Vim personal word lists")
+                .arg(opt_files)
+                .arg(opt_import_format),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_DIFF)
+                .about("Show the words added, removed and counted differently in the new words")
+                .arg(opt_old)
+                .arg(opt_new),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_MERGE)
+                .about("Write the combined word counts of several files to stdout")
+                .arg(opt_models)
+                .arg(opt_strategy)
+                .arg(opt_weight),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_FORGET)
+                .about("Write the word counts without the given words to stdout")
+                .arg(opt_model.clone())
+                .arg(opt_words),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_UNLEARN)
+                .about("Write the word counts less the words in the files to stdout")
+                .arg(opt_model.clone())
+                .arg(opt_unlearn_files),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_DICT_DECAY)
+                .about("Write the word counts multiplied by a factor to stdout, without the words that reach 0")
+                .arg(opt_model)
+                .arg(opt_factor),
+        )
+}
+
+fn run_dict(matches: &ArgMatches, dir: &Path) -> Result<()> {
+    let mut dict = PersonalDictionary::load(dir)?;
+
+    match matches.subcommand() {
+        Some((SUBCOMMAND_DICT_ADD, add_matches)) => {
+            let ignore_list = add_matches.is_present(FLAG_IGNORE_LIST);
+            let bad_words = add_matches.is_present(FLAG_BAD_WORDS);
+            for word in add_matches.values_of(OPT_NAME_DICT_WORDS).unwrap() {
+                if ignore_list {
+                    dict.ignore(word);
+                } else if bad_words {
+                    dict.add_bad_word(word);
+                } else {
+                    dict.add(word);
+                }
+            }
+            dict.save(dir)
+        }
+        Some((SUBCOMMAND_DICT_REMOVE, remove_matches)) => {
+            let ignore_list = remove_matches.is_present(FLAG_IGNORE_LIST);
+            let bad_words = remove_matches.is_present(FLAG_BAD_WORDS);
+            for word in remove_matches.values_of(OPT_NAME_DICT_WORDS).unwrap() {
+                if ignore_list {
+                    dict.unignore(word);
+                } else if bad_words {
+                    dict.remove_bad_word(word);
+                } else {
+                    dict.remove(word);
+                }
+            }
+            dict.save(dir)
+        }
+        Some((SUBCOMMAND_DICT_LIST, list_matches)) => {
+            let words: Vec<&str> = if list_matches.is_present(FLAG_IGNORE_LIST) {
+                dict.ignored().collect()
+            } else if list_matches.is_present(FLAG_BAD_WORDS) {
+                dict.bad_words().collect()
+            } else {
+                dict.words().collect()
+            };
+            for word in words {
+                println!("{}", word);
+            }
+            Ok(())
+        }
+        Some((SUBCOMMAND_DICT_IMPORT, import_matches)) => {
+            let format = import_matches
+                .value_of_t::<ImportFormat>(OPT_NAME_IMPORT_FORMAT)
+                .ok();
+            for file in import_matches.values_of_os(OPT_NAME_DICT_FILES).unwrap() {
+                let path = Path::new(file);
+                let format = format.unwrap_or_else(|| ImportFormat::detect(path));
+                dict.import(&WordList::load(path, format)?);
+            }
+            dict.save(dir)
+        }
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+fn run_dict_diff(matches: &ArgMatches, config: &Config) -> Result<()> {
+    let old = load_model(matches, config, matches.value_of_os(OPT_NAME_OLD).unwrap())?;
+    let new = load_model(matches, config, matches.value_of_os(OPT_NAME_NEW).unwrap())?;
+
+    let diff = old.diff(&new);
+    match output_format(matches, config) {
+        OutputFormat::Plain => print_diff(&diff),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&diff).unwrap()),
+    }
+    Ok(())
+}
+
+fn print_diff(diff: &Diff) {
+    for word in &diff.added {
+        println!("+ {} {}", word.word, word.count);
+    }
+    for word in &diff.removed {
+        println!("- {} {}", word.word, word.count);
+    }
+    for change in &diff.changed {
+        println!("~ {} {} -> {}", change.word, change.before, change.after);
+    }
+}
+
+fn run_dict_merge(matches: &ArgMatches, config: &Config) -> Result<()> {
+    let strategy = match matches.value_of(OPT_NAME_STRATEGY) {
+        Some("max") => MergeStrategy::Max,
+        Some("weighted") => {
+            MergeStrategy::Weighted(matches.value_of_t(OPT_NAME_WEIGHT).unwrap_or(0.5))
+        }
+        _ => MergeStrategy::Sum,
+    };
+
+    let mut files = matches.values_of_os(OPT_NAME_MODELS).unwrap();
+    let mut stava = load_model(matches, config, files.next().unwrap())?;
+    for file in files {
+        stava.merge(&load_model(matches, config, file)?, strategy);
+    }
+    print_word_counts(&stava)
+}
+
+fn run_dict_forget(matches: &ArgMatches, config: &Config) -> Result<()> {
+    let mut stava = load_model(
+        matches,
+        config,
+        matches.value_of_os(OPT_NAME_MODEL).unwrap(),
+    )?;
+    for word in matches.values_of(OPT_NAME_DICT_WORDS).unwrap() {
+        stava.forget(word);
+    }
+    print_word_counts(&stava)
+}
+
+fn run_dict_unlearn(matches: &ArgMatches, config: &Config) -> Result<()> {
+    let mut stava = load_model(
+        matches,
+        config,
+        matches.value_of_os(OPT_NAME_MODEL).unwrap(),
+    )?;
+    for file in matches.values_of_os(OPT_NAME_DICT_FILES).unwrap() {
+        stava.unlearn_from_path(Path::new(file))?;
+    }
+    print_word_counts(&stava)
+}
+
+fn run_dict_decay(matches: &ArgMatches, config: &Config) -> Result<()> {
+    let mut stava = load_model(
+        matches,
+        config,
+        matches.value_of_os(OPT_NAME_MODEL).unwrap(),
+    )?;
+    stava.decay(matches.value_of_t(OPT_NAME_FACTOR).unwrap())?;
+    print_word_counts(&stava)
+}
+
+// Writes `word<TAB>count` lines, which can be read again as saved word counts
+fn print_word_counts(stava: &Stava) -> Result<()> {
+    let stdout = io::stdout();
+    stava
+        .export(
+            io::BufWriter::new(stdout.lock()),
+            ExportFormat::Tsv,
+            &ExportOptions::default(),
+        )
+        .map_err(|err| StavaError::Io {
+            path: PathBuf::from("<stdout>"),
+            source: err,
+        })
+}
+
+// The words of a file of saved word counts, or the words learned from any other file
+fn load_model(matches: &ArgMatches, config: &Config, file: &OsStr) -> Result<Stava> {
+    let path = Path::new(file);
+    let builder = Stava::builder().boxed_tokenizer(tokenizer(matches, config)?);
+    let is_model = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| MODEL_EXTENSIONS.contains(&extension));
+    match is_model {
+        true => builder
+            .frequency_list(FrequencyList::load(path, &FrequencyOptions::default())?)
+            .build(),
+        false => builder.path(path).build(),
+    }
 }
 
 fn exit_with_code(result: StavaResult) -> ! {
@@ -105,16 +1385,34 @@ fn exit_with_code(result: StavaResult) -> ! {
     exit(0)
 }
 
-fn get_default_words() -> &'static str {
-    ASSETS_DIR
-        .get_file("words.txt")
-        .and_then(|file| file.contents_utf8())
-        .unwrap_or_else(|| panic!("Could not get default words"))
+fn exists_on_filesystem(path: &OsStr) -> Result<(), String> {
+    let exists = match path.to_str() {
+        Some(pattern) => walk::expand_glob(pattern).is_ok_and(|paths| !paths.is_empty()),
+        None => Path::new(path).exists(),
+    };
+    match exists {
+        true => Ok(()),
+        false => Err(format!("File not found [{:?}]", path)),
+    }
 }
 
-fn exists_on_filesystem(path: &OsStr) -> Result<(), String> {
-    match Some(path).map(Path::new).map(Path::exists).unwrap_or(false) {
+fn is_file(path: &OsStr) -> Result<(), String> {
+    match Path::new(path).is_file() {
         true => Ok(()),
         false => Err(format!("File not found [{:?}]", path)),
     }
 }
+
+fn dictionary_exists(value: &OsStr) -> Result<(), String> {
+    match value.to_str() {
+        Some(value) => exists_on_filesystem(OsStr::new(&dictionary_spec(value)?.path)),
+        None => exists_on_filesystem(value),
+    }
+}
+
+fn is_positive_number(value: &str) -> Result<(), String> {
+    match value.parse::<f64>() {
+        Ok(number) if number > 0.0 && number.is_finite() => Ok(()),
+        _ => Err(format!("Expected a positive number [{}]", value)),
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::dictionary::Dictionary;
+use crate::error::{Result, StavaError};
+use crate::forbidden::ForbiddenWords;
+use crate::frequency::FrequencyList;
+use crate::hunspell::Hunspell;
+use crate::personal::PersonalDictionary;
+use crate::replacements::Replacements;
+use crate::tokenizer::Tokenizer;
+use crate::{Language, Ranking, Stava};
+
+enum Source {
+    Text(String),
+    Path(PathBuf, Option<Dictionary>),
+    Frequencies(FrequencyList, Option<Dictionary>),
+    Hunspell(Hunspell, Option<Dictionary>),
+}
+
+/// Configures and builds a [`Stava`].
+///
+/// ```no_run
+/// use stava::{Language, Stava};
+///
+/// let stava = Stava::builder()
+///     .default_words(true)
+///     .path("docs/glossary.txt")
+///     .language(Language::English)
+///     .max_distance(1)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct StavaBuilder {
+    // Texts and files to learn words from, in the order they are learned
+    sources: Vec<Source>,
+    // If the default set of words should be learned
+    default_words: bool,
+    personal: Option<PersonalDictionary>,
+    replacements: Replacements,
+    forbidden: ForbiddenWords,
+    tokenizer: Option<Box<dyn Tokenizer>>,
+    alphabet: Option<String>,
+    language: Option<Language>,
+    phonetic_matching: Option<bool>,
+    max_distance: Option<usize>,
+    ranking: Option<Ranking>,
+    min_confidence: Option<f64>,
+}
+
+impl StavaBuilder {
+    pub fn new() -> StavaBuilder {
+        StavaBuilder::default()
+    }
+
+    /// Learn the words in `text`
+    pub fn text(mut self, text: &str) -> Self {
+        self.sources.push(Source::Text(text.to_string()));
+        self
+    }
+
+    /// Learn the words in the file at `path`. Binary files are skipped.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(Source::Path(path.into(), None));
+        self
+    }
+
+    /// Learn the words in the file at `path` as part of `dictionary`
+    pub fn dictionary_path(mut self, path: impl Into<PathBuf>, dictionary: Dictionary) -> Self {
+        self.sources
+            .push(Source::Path(path.into(), Some(dictionary)));
+        self
+    }
+
+    /// Learn the words in a frequency list, each as many times as its count
+    pub fn frequency_list(mut self, list: FrequencyList) -> Self {
+        self.sources.push(Source::Frequencies(list, None));
+        self
+    }
+
+    /// Learn the words in a frequency list as part of `dictionary`
+    pub fn dictionary_frequency_list(
+        mut self,
+        list: FrequencyList,
+        dictionary: Dictionary,
+    ) -> Self {
+        self.sources
+            .push(Source::Frequencies(list, Some(dictionary)));
+        self
+    }
+
+    /// Learn the stems of a Hunspell dictionary and accept the words its affix rules make of them
+    pub fn hunspell(mut self, hunspell: Hunspell) -> Self {
+        self.sources.push(Source::Hunspell(hunspell, None));
+        self
+    }
+
+    /// Learn the stems of a Hunspell dictionary as part of `dictionary`
+    pub fn dictionary_hunspell(mut self, hunspell: Hunspell, dictionary: Dictionary) -> Self {
+        self.sources
+            .push(Source::Hunspell(hunspell, Some(dictionary)));
+        self
+    }
+
+    /// Learn the words in the files at `paths`
+    pub fn paths<P: Into<PathBuf>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.sources.extend(
+            paths
+                .into_iter()
+                .map(|path| Source::Path(path.into(), None)),
+        );
+        self
+    }
+
+    /// Learn the default set of words (default: false)
+    pub fn default_words(mut self, yes: bool) -> Self {
+        self.default_words = yes;
+        self
+    }
+
+    /// Accept the words in the personal dictionary and never suggest the ones in its ignore list
+    pub fn personal_dictionary(mut self, dict: PersonalDictionary) -> Self {
+        self.personal = Some(dict);
+        self
+    }
+
+    /// Always correct the words in `replacements` to their replacement, even if they are known
+    pub fn replacements(mut self, replacements: Replacements) -> Self {
+        self.replacements.extend(replacements);
+        self
+    }
+
+    /// Never accept or suggest the words in `forbidden`, even if they are learned
+    pub fn forbidden(mut self, forbidden: ForbiddenWords) -> Self {
+        self.forbidden.extend(forbidden);
+        self
+    }
+
+    /// How text is split into words (default: [`AsciiTokenizer`](crate::tokenizer::AsciiTokenizer))
+    pub fn tokenizer(self, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.boxed_tokenizer(Box::new(tokenizer))
+    }
+
+    pub fn boxed_tokenizer(mut self, tokenizer: Box<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Use the alphabet and phonetic matching of `language` (default: [`Language::English`])
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// If words that sound the same are suggested as corrections (default: given by the language)
+    pub fn phonetic_matching(mut self, yes: bool) -> Self {
+        self.phonetic_matching = Some(yes);
+        self
+    }
+
+    /// The letters used when editing words (default: the alphabet of the language)
+    pub fn alphabet(mut self, letters: &str) -> Self {
+        self.alphabet = Some(letters.to_string());
+        self
+    }
+
+    /// The maximum number of edits between a word and its correction (default: 2)
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// How the correction is chosen among the candidates (default: [`Ranking::Norvig`])
+    pub fn ranking(mut self, ranking: Ranking) -> Self {
+        self.ranking = Some(ranking);
+        self
+    }
+
+    /// Corrections less confident than this, from 0 to 1, are not made and the word is returned
+    /// as it is (default: 0)
+    pub fn min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = Some(min_confidence);
+        self
+    }
+
+    pub fn build(self) -> Result<Stava> {
+        let mut stava = Stava::default();
+
+        if let Some(tokenizer) = self.tokenizer {
+            stava.tokenizer = tokenizer;
+        }
+        let language = self.language.unwrap_or(Language::English);
+        stava.language = language;
+        stava.set_phonetic_matching(
+            self.phonetic_matching
+                .unwrap_or_else(|| language.has_phonetic_matching()),
+        );
+
+        let alphabet = self
+            .alphabet
+            .unwrap_or_else(|| language.alphabet().to_string());
+        let mut seen = HashSet::new();
+        let letters: Vec<String> = alphabet
+            .chars()
+            .filter(|letter| seen.insert(*letter))
+            .map(String::from)
+            .collect();
+        if letters.is_empty() {
+            return Err(StavaError::invalid_setting(
+                "alphabet",
+                "The alphabet can not be empty",
+            ));
+        }
+        if let Some(letter) = letters
+            .iter()
+            .find(|letter| *letter == "'" || letter.trim().is_empty())
+        {
+            return Err(StavaError::invalid_setting(
+                "alphabet",
+                format!(
+                    "Expected letters, not whitespace or apostrophes [{:?}]",
+                    letter
+                ),
+            ));
+        }
+        stava.alphabet = letters;
+
+        stava.replacements = self.replacements;
+        stava.forbidden = self.forbidden;
+        if let Some(max_distance) = self.max_distance {
+            stava.max_distance = max_distance;
+        }
+        if let Some(ranking) = self.ranking {
+            if let Ranking::Combined { edit_penalty } = ranking {
+                if !(edit_penalty >= 1.0 && edit_penalty.is_finite()) {
+                    return Err(StavaError::invalid_setting(
+                        "edit-penalty",
+                        format!("Expected a number of at least 1 [{}]", edit_penalty),
+                    ));
+                }
+            }
+            stava.ranking = ranking;
+        }
+        if let Some(min_confidence) = self.min_confidence {
+            if !(0.0..=1.0).contains(&min_confidence) {
+                return Err(StavaError::invalid_setting(
+                    "min-confidence",
+                    format!("Expected a number between 0 and 1 [{}]", min_confidence),
+                ));
+            }
+            stava.min_confidence = min_confidence;
+        }
+
+        if self.default_words {
+            stava.learn_default_words()?;
+        }
+        for source in self.sources {
+            match source {
+                Source::Text(text) => stava.learn(&text),
+                Source::Path(path, None) => stava.learn_from_path(&path)?,
+                Source::Path(path, Some(dictionary)) => {
+                    stava.learn_from_path_as(&dictionary, &path)?
+                }
+                Source::Frequencies(list, None) => stava.learn_frequencies(&list),
+                Source::Frequencies(list, Some(dictionary)) => {
+                    stava.learn_frequencies_as(&dictionary, &list)
+                }
+                Source::Hunspell(hunspell, None) => stava.learn_hunspell(hunspell),
+                Source::Hunspell(hunspell, Some(dictionary)) => {
+                    stava.learn_hunspell_as(&dictionary, hunspell)
+                }
+            }
+        }
+        if let Some(dict) = self.personal {
+            stava.merge_personal(&dict);
+        }
+
+        Ok(stava)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use regex::Regex;
+
+    use std::fs;
+
+    #[test]
+    fn test_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        fs::write(&path, "spelling bicycle").unwrap();
+
+        let stava = Stava::builder()
+            .text("spelling")
+            .path(&path)
+            .build()
+            .unwrap();
+
+        assert_eq!(stava.count("spelling"), Some(2));
+        assert_eq!(stava.count("bicycle"), Some(1));
+        assert_eq!(stava.len(), 2);
+    }
+
+    #[test]
+    fn test_build_with_settings() {
+        let stava = Stava::builder()
+            .text("Smörgåsbord and 3000 poetry")
+            .tokenizer(Regex::new(r"[a-zåäö0-9]+").unwrap())
+            .alphabet("abcdefghijklmnopqrstuvwxyzåäö")
+            .max_distance(1)
+            .phonetic_matching(false)
+            .build()
+            .unwrap();
+
+        assert!(stava.contains("smörgåsbord"));
+        assert!(stava.contains("3000"));
+        assert_eq!(stava.correct("smorgåsbord").unwrap().word, "smörgåsbord");
+        assert!(!stava.correct("peotryy").unwrap().was_corrected);
+    }
+
+    #[test]
+    fn test_build_with_duplicate_letters() {
+        let stava = Stava::builder().alphabet("abcab").build().unwrap();
+
+        assert_eq!(stava.alphabet, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_build_with_invalid_alphabet() {
+        for alphabet in ["", "ab c", "ab'", "ab\t"] {
+            let actual = Stava::builder().alphabet(alphabet).build();
+
+            assert!(matches!(actual, Err(StavaError::InvalidSetting { .. })));
+        }
+    }
+
+    #[test]
+    fn test_build_with_personal_dictionary() {
+        let mut dict = PersonalDictionary::default();
+        dict.add("rustacean");
+
+        let stava = Stava::builder().personal_dictionary(dict).build().unwrap();
+
+        assert!(stava.contains("rustacean"));
+    }
+
+    #[test]
+    fn test_build_with_dictionaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let words = dir.path().join("words.txt");
+        let glossary = dir.path().join("glossary.txt");
+        fs::write(&words, "spelling spelling spelling bicycle").unwrap();
+        fs::write(&glossary, "speling").unwrap();
+
+        let stava = Stava::builder()
+            .path(&words)
+            .dictionary_path(&glossary, Dictionary::new("glossary").with_weight(10))
+            .build()
+            .unwrap();
+
+        assert_eq!(stava.count("speling"), Some(10));
+        assert_eq!(stava.dictionary_of("speling").unwrap().name, "glossary");
+        assert_eq!(stava.dictionary_of("spelling"), None);
+    }
+
+    #[test]
+    fn test_build_with_invalid_min_confidence() {
+        let actual = Stava::builder().min_confidence(5.0).build();
+
+        assert!(matches!(actual, Err(StavaError::InvalidSetting { .. })));
+        assert!(Stava::builder().min_confidence(f64::NAN).build().is_err());
+        assert!(Stava::builder().min_confidence(1.0).build().is_ok());
+    }
+
+    #[test]
+    fn test_build_with_invalid_edit_penalty() {
+        for edit_penalty in [0.0, 0.5, f64::INFINITY, f64::NAN] {
+            let actual = Stava::builder()
+                .ranking(Ranking::Combined { edit_penalty })
+                .build();
+
+            assert!(matches!(actual, Err(StavaError::InvalidSetting { .. })));
+        }
+        assert!(Stava::builder()
+            .ranking(Ranking::combined())
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_build_with_missing_file() {
+        let actual = Stava::builder().path("some_non_existing_file").build();
+
+        assert!(matches!(actual, Err(StavaError::Io { .. })));
+    }
+}
@@ -0,0 +1,277 @@
+use serde::Deserialize;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::dictionary::DictionarySpec;
+use crate::error::{Result, StavaError};
+use crate::frequency::FrequencyOptions;
+use crate::tokenizer::TokenizerKind;
+use crate::walk::{self, FileFilter};
+use crate::{Language, Ranking};
+
+pub const CONFIG_FILE_NAME: &str = ".stava.toml";
+
+/// Project configuration read from a `.stava.toml` file.
+///
+/// Every setting is optional, settings given as CLI flags take precedence.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    // The language of the words
+    pub language: Option<Language>,
+    // Files to learn words from, each written as `path[:weight[:priority]]`
+    pub dictionaries: Vec<DictionarySpec>,
+    // Glob patterns of files to learn words from
+    pub files: Vec<String>,
+    // Frequency lists to learn words from, each written as `path[:weight[:priority]]`
+    pub frequency_lists: Vec<DictionarySpec>,
+    // Hunspell `.dic` files to learn words from, each written as `path[:weight[:priority]]`
+    pub hunspell: Vec<DictionarySpec>,
+    // Words counted fewer times than this in a frequency list are skipped
+    pub min_count: Option<u64>,
+    // The counts in frequency lists are multiplied by this
+    pub count_scale: Option<f64>,
+    // If the default set of words should be included
+    pub default_words: Option<bool>,
+    // Regex patterns of words that are never corrected
+    pub ignore: Vec<String>,
+    // Files of `wrong -> right` replacements that are always made
+    pub replacements: Vec<PathBuf>,
+    // Files of words that are never accepted, with an optional `-> preferred` word
+    pub forbidden: Vec<PathBuf>,
+    // Glob patterns of files to check when walking directories
+    pub include: Vec<String>,
+    // Glob patterns of files to skip when walking directories
+    pub exclude: Vec<String>,
+    // The maximum number of edits between a word and its correction
+    pub max_distance: Option<usize>,
+    // Corrections less confident than this, from 0 to 1, are not made
+    pub min_confidence: Option<f64>,
+    // How the correction is chosen among the candidates
+    pub ranking: Option<Ranking>,
+    // How many times as frequent a candidate with one more edit must be with the combined ranking,
+    // which it switches on unless another ranking is given
+    pub edit_penalty: Option<f64>,
+    // How text is split into words
+    pub tokenizer: Option<TokenizerKind>,
+    // Regex pattern matching the words in lowercased text, used instead of `tokenizer`
+    pub token_regex: Option<String>,
+    // How results are printed
+    pub format: Option<OutputFormat>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Plain,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(OutputFormat::Plain),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("Unsupported output format [{}]", s)),
+        }
+    }
+}
+
+impl Config {
+    /// Find the closest `.stava.toml` in `dir` or any of its ancestors and read it.
+    ///
+    /// Relative paths in the config are resolved from the directory the config file is in.
+    pub fn discover(dir: &Path) -> Result<Option<Config>> {
+        match dir
+            .ancestors()
+            .map(|ancestor| ancestor.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+        {
+            Some(path) => Config::load(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the config from `path`
+    pub fn load(path: &Path) -> Result<Config> {
+        let contents = fs::read_to_string(path).map_err(|err| StavaError::io(path, err))?;
+        let mut config: Config =
+            toml::from_str(&contents).map_err(|err| StavaError::InvalidConfig {
+                path: path.to_path_buf(),
+                message: err.to_string(),
+            })?;
+
+        if let Some(base) = path.parent() {
+            config.resolve_paths(base);
+        }
+
+        Ok(config)
+    }
+
+    /// The files matching the glob patterns in `files`
+    pub fn matching_files(&self) -> Result<Vec<PathBuf>> {
+        let mut result = Vec::new();
+        for pattern in &self.files {
+            for path in walk::expand_glob(pattern)? {
+                if path.is_file() {
+                    result.push(path);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// The filter given by `include` and `exclude`
+    pub fn file_filter(&self) -> FileFilter {
+        FileFilter {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+        }
+    }
+
+    /// How the counts of frequency lists are adjusted, given by `min-count` and `count-scale`
+    pub fn frequency_options(&self) -> FrequencyOptions {
+        let default = FrequencyOptions::default();
+        FrequencyOptions {
+            min_count: self.min_count.unwrap_or(default.min_count),
+            scale: self.count_scale.unwrap_or(default.scale),
+        }
+    }
+
+    fn resolve_paths(&mut self, base: &Path) {
+        for spec in self
+            .dictionaries
+            .iter_mut()
+            .chain(self.frequency_lists.iter_mut())
+            .chain(self.hunspell.iter_mut())
+        {
+            spec.path = base.join(&spec.path).to_string_lossy().to_string();
+        }
+        for path in self
+            .replacements
+            .iter_mut()
+            .chain(self.forbidden.iter_mut())
+        {
+            *path = base.join(&path);
+        }
+        for pattern in self.files.iter_mut() {
+            if Path::new(pattern).is_relative() {
+                let escaped_base = glob::Pattern::escape(&base.to_string_lossy());
+                *pattern = format!("{}/{}", escaped_base, pattern);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            r#"
+            language = "en"
+            dictionaries = ["docs/glossary.txt:10"]
+            files = ["**/*.md"]
+            frequency-lists = ["counts.tsv"]
+            hunspell = ["en_US.dic"]
+            min-count = 5
+            count-scale = 0.5
+            default-words = true
+            ignore = ["^[A-Z]+$"]
+            replacements = ["typos.txt"]
+            forbidden = ["forbidden.txt"]
+            include = ["*.md"]
+            exclude = ["CHANGELOG.md"]
+            max-distance = 1
+            min-confidence = 0.25
+            ranking = "combined"
+            edit-penalty = 50
+            tokenizer = "unicode"
+            token-regex = "[a-z']+"
+            format = "json"
+            "#,
+        )
+        .unwrap();
+
+        let actual = Config::load(&path).unwrap();
+        let expected = Config {
+            language: Some(Language::English),
+            dictionaries: vec![DictionarySpec {
+                path: dir.path().join("docs/glossary.txt").display().to_string(),
+                weight: 10,
+                ..DictionarySpec::new("docs/glossary.txt")
+            }],
+            files: vec![format!("{}/**/*.md", dir.path().display())],
+            frequency_lists: vec![DictionarySpec {
+                path: dir.path().join("counts.tsv").display().to_string(),
+                ..DictionarySpec::new("counts.tsv")
+            }],
+            hunspell: vec![DictionarySpec {
+                path: dir.path().join("en_US.dic").display().to_string(),
+                ..DictionarySpec::new("en_US.dic")
+            }],
+            min_count: Some(5),
+            count_scale: Some(0.5),
+            default_words: Some(true),
+            ignore: vec!["^[A-Z]+$".to_string()],
+            replacements: vec![dir.path().join("typos.txt")],
+            forbidden: vec![dir.path().join("forbidden.txt")],
+            include: vec!["*.md".to_string()],
+            exclude: vec!["CHANGELOG.md".to_string()],
+            max_distance: Some(1),
+            min_confidence: Some(0.25),
+            ranking: Some(Ranking::combined()),
+            edit_penalty: Some(50.0),
+            tokenizer: Some(TokenizerKind::Unicode),
+            token_regex: Some("[a-z']+".to_string()),
+            format: Some(OutputFormat::Json),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "max-distanse = 1").unwrap();
+
+        let actual = Config::load(&path).unwrap_err();
+
+        assert!(matches!(actual, StavaError::InvalidConfig { .. }));
+    }
+
+    #[test]
+    fn test_discover_walks_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.path().join(CONFIG_FILE_NAME), "max-distance = 1").unwrap();
+
+        let actual = Config::discover(&nested).unwrap();
+
+        assert_eq!(actual.and_then(|config| config.max_distance), Some(1));
+    }
+
+    #[test]
+    fn test_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/a.md"), "").unwrap();
+        fs::write(dir.path().join("docs/b.txt"), "").unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, r#"files = ["**/*.md"]"#).unwrap();
+
+        let actual = Config::load(&path).unwrap().matching_files().unwrap();
+
+        assert_eq!(actual, vec![dir.path().join("docs/a.md")]);
+    }
+}
@@ -0,0 +1,153 @@
+use serde::Deserialize;
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// The name of the dictionary of the default set of words
+pub const DEFAULT_DICTIONARY_NAME: &str = "default";
+/// The name of the dictionary of the words in the personal dictionary
+pub const PERSONAL_DICTIONARY_NAME: &str = "personal";
+
+/// A named source of learned words, reported with the corrections it supplies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dictionary {
+    pub name: String,
+    // Every occurrence of a word counts this many times
+    pub weight: u32,
+    // Candidates from a dictionary with higher priority are preferred, regardless of their count
+    pub priority: i32,
+}
+
+impl Dictionary {
+    pub fn new(name: &str) -> Dictionary {
+        Dictionary {
+            name: name.to_string(),
+            weight: 1,
+            priority: 0,
+        }
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// A file or pattern of files to learn words from, with the weight and priority of its words.
+///
+/// Written as `path`, `path:weight` or `path:weight:priority`, e.g. `glossary.txt:10`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+pub struct DictionarySpec {
+    // The name of the dictionary, the path as it was written
+    pub name: String,
+    pub path: String,
+    pub weight: u32,
+    pub priority: i32,
+}
+
+impl DictionarySpec {
+    /// A spec of the path with the default weight and priority
+    pub fn new(path: &str) -> DictionarySpec {
+        DictionarySpec {
+            name: path.to_string(),
+            path: path.to_string(),
+            weight: 1,
+            priority: 0,
+        }
+    }
+
+    /// The dictionary of the spec
+    pub fn dictionary(&self) -> Dictionary {
+        Dictionary::new(&self.name)
+            .with_weight(self.weight)
+            .with_priority(self.priority)
+    }
+}
+
+impl FromStr for DictionarySpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Only number suffixes are split off so paths containing `:` are kept whole
+        let mut path = s;
+        let mut numbers = Vec::new();
+        while numbers.len() < 2 {
+            match path.rsplit_once(':') {
+                Some((rest, number)) if is_integer(number) => {
+                    numbers.insert(0, number);
+                    path = rest;
+                }
+                _ => break,
+            }
+        }
+        if path.is_empty() {
+            return Err(format!("Missing path in [{}]", s));
+        }
+
+        let mut spec = DictionarySpec::new(path);
+        if let Some(weight) = numbers.first() {
+            spec.weight = match weight.parse() {
+                Ok(weight) if weight > 0 => weight,
+                _ => return Err(format!("Invalid weight in [{}], it must be at least 1", s)),
+            };
+        }
+        if let Some(priority) = numbers.get(1) {
+            spec.priority = priority
+                .parse()
+                .map_err(|_| format!("Invalid priority in [{}]", s))?;
+        }
+        Ok(spec)
+    }
+}
+
+impl TryFrom<String> for DictionarySpec {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(path: &str, weight: u32, priority: i32) -> DictionarySpec {
+        DictionarySpec {
+            weight,
+            priority,
+            ..DictionarySpec::new(path)
+        }
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!("words.txt".parse(), Ok(spec("words.txt", 1, 0)));
+        assert_eq!("glossary.txt:10".parse(), Ok(spec("glossary.txt", 10, 0)));
+        assert_eq!("glossary.txt:10:2".parse(), Ok(spec("glossary.txt", 10, 2)));
+        assert_eq!(
+            "glossary.txt:10:-1".parse(),
+            Ok(spec("glossary.txt", 10, -1))
+        );
+        assert_eq!("docs/*.md:3".parse(), Ok(spec("docs/*.md", 3, 0)));
+        assert_eq!("C:/words.txt".parse(), Ok(spec("C:/words.txt", 1, 0)));
+        assert_eq!("a:b:c:5".parse(), Ok(spec("a:b:c", 5, 0)));
+    }
+
+    #[test]
+    fn test_parse_invalid_spec() {
+        assert!("glossary.txt:0".parse::<DictionarySpec>().is_err());
+        assert!(":10".parse::<DictionarySpec>().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use serde::Serialize;
+
+use crate::stats::WordCount;
+use crate::Stava;
+
+/// The words added, removed and counted differently between two [`Stava`]
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Diff {
+    // The words only in the new words, the most frequent first
+    pub added: Vec<WordCount>,
+    // The words only in the old words, the most frequent first
+    pub removed: Vec<WordCount>,
+    // The words in both with different counts, the largest shift in frequency first
+    pub changed: Vec<CountChange>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CountChange {
+    pub word: String,
+    pub before: u32,
+    pub after: u32,
+    // The change of the share of all learned words the word makes up
+    pub frequency_change: f64,
+}
+
+impl Stava {
+    /// The differences between these words and the `new` ones
+    pub fn diff(&self, new: &Stava) -> Diff {
+        let mut diff = Diff::default();
+        for (word, &count) in &new.words_w_count {
+            match self.words_w_count.get(word) {
+                None => diff.added.push(WordCount {
+                    word: word.clone(),
+                    count,
+                }),
+                Some(&before) if before != count => diff.changed.push(CountChange {
+                    word: word.clone(),
+                    before,
+                    after: count,
+                    frequency_change: frequency(count, new.total_count)
+                        - frequency(before, self.total_count),
+                }),
+                Some(_) => {}
+            }
+        }
+        for (word, &count) in &self.words_w_count {
+            if !new.words_w_count.contains_key(word) {
+                diff.removed.push(WordCount {
+                    word: word.clone(),
+                    count,
+                });
+            }
+        }
+
+        for words in [&mut diff.added, &mut diff.removed] {
+            words.sort_unstable_by(|a, b| b.count.cmp(&a.count).then(a.word.cmp(&b.word)));
+        }
+        diff.changed.sort_unstable_by(|a, b| {
+            b.frequency_change
+                .abs()
+                .total_cmp(&a.frequency_change.abs())
+                .then(a.word.cmp(&b.word))
+        });
+        diff
+    }
+}
+
+fn frequency(count: u32, total_count: u64) -> f64 {
+    match total_count {
+        0 => 0.0,
+        _ => f64::from(count) / total_count as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut old = Stava::default();
+        old.learn("the cat and the bird and the fish");
+        let mut new = Stava::default();
+        new.learn("the cat and the cat and the dog");
+
+        let actual = old.diff(&new);
+
+        assert_eq!(
+            actual.added,
+            vec![WordCount {
+                word: "dog".to_string(),
+                count: 1
+            }]
+        );
+        assert_eq!(
+            actual
+                .removed
+                .iter()
+                .map(|word| word.word.as_str())
+                .collect::<Vec<_>>(),
+            vec!["bird", "fish"]
+        );
+        assert_eq!(actual.changed.len(), 1);
+        assert_eq!(actual.changed[0].word, "cat");
+        assert_eq!(actual.changed[0].before, 1);
+        assert_eq!(actual.changed[0].after, 2);
+        assert_eq!(actual.changed[0].frequency_change, 0.125);
+    }
+
+    #[test]
+    fn test_diff_of_same_words_is_empty() {
+        let mut stava = Stava::default();
+        stava.learn("the cat");
+
+        assert_eq!(stava.diff(&stava), Diff::default());
+    }
+}
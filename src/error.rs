@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum StavaError {
+    // A file or directory could not be read or written
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    // The default set of words is not included in the build
+    MissingDefaultWords,
+    // No directory could be found for the personal dictionary
+    MissingDataDir,
+    // The word can not be corrected, e.g. because it contains non-ASCII characters
+    InvalidWord(String),
+    // The config file could not be parsed
+    InvalidConfig {
+        path: PathBuf,
+        message: String,
+    },
+    // A glob or regex pattern could not be parsed
+    InvalidPattern {
+        pattern: String,
+        message: String,
+    },
+    // A line in a file of words could not be parsed
+    InvalidFile {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    // A setting is out of its range, e.g. a minimum confidence above 1
+    InvalidSetting {
+        name: String,
+        message: String,
+    },
+}
+
+pub type Result<T, E = StavaError> = std::result::Result<T, E>;
+
+impl StavaError {
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> StavaError {
+        StavaError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// An [`InvalidSetting`](StavaError::InvalidSetting) error for the setting `name`
+    pub fn invalid_setting(name: &str, message: impl ToString) -> StavaError {
+        StavaError::InvalidSetting {
+            name: name.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn invalid_pattern(pattern: &str, message: impl ToString) -> StavaError {
+        StavaError::InvalidPattern {
+            pattern: pattern.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for StavaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StavaError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            StavaError::MissingDefaultWords => write!(f, "Could not get default words"),
+            StavaError::MissingDataDir => write!(
+                f,
+                "Could not find a directory for the personal dictionary, use --dict-dir"
+            ),
+            StavaError::InvalidWord(word) => write!(
+                f,
+                "Invalid word [{}], only words from the English alphabet are supported",
+                word
+            ),
+            StavaError::InvalidConfig { path, message } => {
+                write!(f, "Invalid config file {}: {}", path.display(), message)
+            }
+            StavaError::InvalidPattern { pattern, message } => {
+                write!(f, "Invalid pattern [{}]: {}", pattern, message)
+            }
+            StavaError::InvalidFile {
+                path,
+                line,
+                message,
+            } => write!(f, "Invalid file {}:{}: {}", path.display(), line, message),
+            StavaError::InvalidSetting { name, message } => {
+                write!(f, "Invalid {}: {}", name, message)
+            }
+        }
+    }
+}
+
+impl Error for StavaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StavaError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
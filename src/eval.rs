@@ -0,0 +1,221 @@
+use serde::Serialize;
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::error::{Result, StavaError};
+use crate::{normalize, Stava};
+
+/// Misspelled words with their right spelling, to measure how well words are corrected.
+///
+/// The set is read from files with one `right: wrong1 wrong2` per line, like the test sets Peter
+/// Norvig derived from the Birkbeck spelling error corpus. Empty lines and lines starting with `#`
+/// are skipped.
+#[derive(Debug, Default, PartialEq)]
+pub struct TestSet {
+    // The misspelled words with their right spelling
+    cases: Vec<(String, String)>,
+}
+
+impl TestSet {
+    /// Read the set from `path`
+    pub fn load(path: &Path) -> Result<TestSet> {
+        let contents = fs::read_to_string(path).map_err(|err| StavaError::io(path, err))?;
+        let mut result = TestSet::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (right, wrongs) = line
+                .split_once(':')
+                .filter(|(right, wrongs)| !right.trim().is_empty() && !wrongs.trim().is_empty())
+                .ok_or_else(|| StavaError::InvalidFile {
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                    message: "Expected a line like `right: wrong1 wrong2`".to_string(),
+                })?;
+            for wrong in wrongs.split_whitespace() {
+                result.insert(right.trim(), wrong);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Add a case where `wrong` should be corrected to `right`
+    pub fn insert(&mut self, right: &str, wrong: &str) {
+        self.cases.push((
+            normalize(&right.to_lowercase()),
+            normalize(&wrong.to_lowercase()),
+        ));
+    }
+
+    /// The right and misspelled words, in the order they were read
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cases
+            .iter()
+            .map(|(right, wrong)| (right.as_str(), wrong.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+}
+
+/// How well the words of a [`TestSet`] were corrected
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Evaluation {
+    // The number of misspelled words
+    pub words: usize,
+    // The number of misspelled words corrected to the right spelling
+    pub correct: usize,
+    // The share of the words that were corrected to the right spelling
+    pub accuracy: f64,
+    // The number of words whose right spelling is not learned, so they can not be corrected
+    pub unknown: usize,
+    // The share of the words whose right spelling is not learned
+    pub unknown_rate: f64,
+    // The time it took to correct the words
+    pub seconds: f64,
+    pub words_per_second: f64,
+    // The words that were not corrected to the right spelling, those with the most frequent right
+    // spelling first
+    pub failures: Vec<Failure>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Failure {
+    pub wrong: String,
+    pub right: String,
+    // How many times the right spelling was learned, if it was
+    pub right_count: Option<u32>,
+    // The word that was returned, if the misspelled word could be corrected at all
+    pub suggestion: Option<String>,
+    // How many times the returned word was learned, if it was
+    pub suggestion_count: Option<u32>,
+}
+
+impl Stava {
+    /// Correct the words of `set` and compare them to their right spelling, keeping the `worst`
+    /// failures
+    pub fn evaluate(&self, set: &TestSet, worst: usize) -> Evaluation {
+        let start = Instant::now();
+        let results: Vec<_> = set.iter().map(|(_, wrong)| self.correct(wrong)).collect();
+        let seconds = start.elapsed().as_secs_f64();
+
+        let mut correct = 0;
+        let mut unknown = 0;
+        let mut failures = Vec::new();
+        for ((right, wrong), result) in set.iter().zip(results) {
+            let right_count = self.count(right);
+            if right_count.is_none() {
+                unknown += 1;
+            }
+
+            let suggestion = result.ok().map(|result| result.word);
+            if suggestion.as_deref() == Some(right) {
+                correct += 1;
+                continue;
+            }
+            failures.push(Failure {
+                wrong: wrong.to_string(),
+                right: right.to_string(),
+                right_count,
+                suggestion_count: suggestion.as_deref().and_then(|word| self.count(word)),
+                suggestion,
+            });
+        }
+        failures.sort_by_key(|failure| Reverse(failure.right_count));
+        failures.truncate(worst);
+
+        let share = |count: usize| match set.len() {
+            0 => 0.0,
+            words => count as f64 / words as f64,
+        };
+        Evaluation {
+            words: set.len(),
+            correct,
+            accuracy: share(correct),
+            unknown,
+            unknown_rate: share(unknown),
+            seconds,
+            words_per_second: match seconds > 0.0 {
+                true => set.len() as f64 / seconds,
+                false => 0.0,
+            },
+            failures,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(contents: &str) -> Result<TestSet> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("testset.txt");
+        fs::write(&path, contents).unwrap();
+        TestSet::load(&path)
+    }
+
+    #[test]
+    fn test_load() {
+        let actual = load("# Birkbeck\nSpelling: speling spellng\n\naccess: acess\n").unwrap();
+
+        assert_eq!(
+            actual.iter().collect::<Vec<_>>(),
+            vec![
+                ("spelling", "speling"),
+                ("spelling", "spellng"),
+                ("access", "acess")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_line() {
+        let actual = load("spelling: speling\nacess\n");
+
+        assert!(matches!(
+            actual,
+            Err(StavaError::InvalidFile { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut stava = Stava::default();
+        stava.learn("spelling spelling access spewing");
+        let mut set = TestSet::default();
+        set.insert("spelling", "speling");
+        set.insert("access", "acess");
+        set.insert("spewing", "spelling");
+        set.insert("rustacean", "rustacen");
+
+        let actual = stava.evaluate(&set, 1);
+
+        assert_eq!(actual.words, 4);
+        assert_eq!(actual.correct, 2);
+        assert_eq!(actual.accuracy, 0.5);
+        assert_eq!(actual.unknown, 1);
+        assert_eq!(actual.unknown_rate, 0.25);
+        assert_eq!(
+            actual.failures,
+            vec![Failure {
+                wrong: "spelling".to_string(),
+                right: "spewing".to_string(),
+                right_count: Some(1),
+                suggestion: Some("spelling".to_string()),
+                suggestion_count: Some(2),
+            }]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use serde::Serialize;
+
+use std::fmt;
+
+use crate::error::Result;
+use crate::{Stava, StavaResult};
+
+/// Why a correction was made, or why it was not
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    // The word has a replacement, which is always made
+    Replacement,
+    // The word is forbidden and has a preferred word
+    Preferred,
+    // The word is learned and not corrected
+    Known,
+    // Inserting an apostrophe makes a learned word, which is preferred over other edits
+    MissingApostrophe,
+    // No edits are close enough, but the candidate sounds like the word
+    SoundAlike,
+    // The only candidate
+    OnlyCandidate,
+    // The candidate with the fewest edits from the dictionary with the highest priority
+    HighestPriority,
+    // The most frequent candidate with the fewest edits
+    MostFrequent,
+    // The candidate with the highest count after the penalty for its edits
+    HighestScore,
+    // The first in alphabetical order of the equally ranked candidates
+    Alphabetical,
+    // The best candidate is less confident than the minimum confidence, so no correction is made
+    LowConfidence,
+    // No learned word is close enough to the word
+    NoCandidate,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Rule::Replacement => "the word has a replacement",
+            Rule::Preferred => "the word is forbidden and has a preferred word",
+            Rule::Known => "the word is learned",
+            Rule::MissingApostrophe => "a missing apostrophe is the most likely mistake",
+            Rule::SoundAlike => "no edits are close enough, but the candidate sounds alike",
+            Rule::OnlyCandidate => "the only candidate",
+            Rule::HighestPriority => "the candidate from the dictionary with the highest priority",
+            Rule::MostFrequent => "the most frequent of the candidates with the fewest edits",
+            Rule::HighestScore => "the highest count after the penalty for the edits",
+            Rule::Alphabetical => {
+                "the first of the equally ranked candidates in alphabetical order"
+            }
+            Rule::LowConfidence => "the best candidate is below the minimum confidence",
+            Rule::NoCandidate => "no learned word is close enough",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// An edit of a word. Positions are in characters of the misspelled word, starting at 1.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Edit {
+    Delete {
+        position: usize,
+        letter: char,
+    },
+    // The letters at `position` and the one after change places
+    Transpose {
+        position: usize,
+        letters: String,
+    },
+    Replace {
+        position: usize,
+        from: char,
+        to: char,
+    },
+    // The letter is inserted before `position`
+    Insert {
+        position: usize,
+        letter: char,
+    },
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edit::Delete { position, letter } => write!(f, "delete '{}' at {}", letter, position),
+            Edit::Transpose { position, letters } => {
+                write!(f, "transpose '{}' at {}", letters, position)
+            }
+            Edit::Replace { position, from, to } => {
+                write!(f, "replace '{}' with '{}' at {}", from, to, position)
+            }
+            Edit::Insert { position, letter } => write!(f, "insert '{}' at {}", letter, position),
+        }
+    }
+}
+
+/// A learned word that was considered as the correction
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Candidate {
+    pub word: String,
+    pub count: u32,
+    // The priority of the dictionary the word is reported as coming from
+    pub priority: i32,
+    // The number of edits from the misspelled word it was ranked with
+    pub distance: usize,
+}
+
+/// How the correction of a word was chosen
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Explanation {
+    // The word that was corrected, normalized
+    pub input: String,
+    #[serde(flatten)]
+    pub result: StavaResult,
+    pub rule: Rule,
+    // The number of edits from the input to the returned word
+    pub distance: usize,
+    // The edits that turn the input into the returned word, from left to right
+    pub edits: Vec<Edit>,
+    // The candidates that were ranked, the best first. These are the ones with the fewest edits,
+    // or all of them with the combined ranking.
+    pub candidates: Vec<Candidate>,
+}
+
+// What is recorded while correcting a word to explain the correction
+#[derive(Debug, Default)]
+pub(crate) struct Trace {
+    pub(crate) rule: Option<Rule>,
+    pub(crate) candidates: Vec<Candidate>,
+}
+
+pub(crate) fn note_rule(trace: &mut Option<&mut Trace>, rule: Rule) {
+    if let Some(trace) = trace {
+        trace.rule = Some(rule);
+    }
+}
+
+impl Stava {
+    /// Correct `word` like [`correct`](Stava::correct) and explain how the correction was chosen
+    pub fn explain(&self, word: &str) -> Result<Explanation> {
+        // Invalid words are rejected the same way as when correcting
+        let normalized = self.normalize_valid(word)?;
+
+        let mut trace = Trace::default();
+        let result = self.correct_traced(&normalized, Some(&mut trace));
+        let edits = edit_path(&normalized, &result.word);
+        Ok(Explanation {
+            rule: trace.rule.unwrap_or(Rule::NoCandidate),
+            distance: edits.len(),
+            edits,
+            candidates: trace.candidates,
+            input: normalized,
+            result,
+        })
+    }
+}
+
+/// The fewest deletes, transposes of adjacent letters, replaces and inserts that turn `from` into
+/// `to`, where no letter is edited twice
+pub fn edit_path(from: &str, to: &str) -> Vec<Edit> {
+    let a: Vec<char> = from.chars().collect();
+    let b: Vec<char> = to.chars().collect();
+
+    // distances[i][j] is the distance between the first i letters of `from` and j letters of `to`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    distances[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if is_transpose(&a, &b, i, j) {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        let distance = distances[i][j];
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && distances[i - 1][j - 1] == distance {
+            i -= 1;
+            j -= 1;
+        } else if is_transpose(&a, &b, i, j) && distances[i - 2][j - 2] + 1 == distance {
+            edits.push(Edit::Transpose {
+                position: i - 1,
+                letters: a[i - 2..i].iter().collect(),
+            });
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && j > 0 && distances[i - 1][j - 1] + 1 == distance {
+            edits.push(Edit::Replace {
+                position: i,
+                from: a[i - 1],
+                to: b[j - 1],
+            });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && distances[i - 1][j] + 1 == distance {
+            edits.push(Edit::Delete {
+                position: i,
+                letter: a[i - 1],
+            });
+            i -= 1;
+        } else {
+            edits.push(Edit::Insert {
+                position: i + 1,
+                letter: b[j - 1],
+            });
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+// If the letters before `i` in `a` are the ones before `j` in `b` in the other order
+fn is_transpose(a: &[char], b: &[char], i: usize, j: usize) -> bool {
+    i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] && a[i - 1] != a[i - 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::StavaError;
+
+    #[test]
+    fn test_edit_path() {
+        assert_eq!(edit_path("spelling", "spelling"), vec![]);
+        assert_eq!(
+            edit_path("speling", "spelling"),
+            vec![Edit::Insert {
+                position: 4,
+                letter: 'l'
+            }]
+        );
+        assert_eq!(
+            edit_path("recieve", "receive"),
+            vec![Edit::Transpose {
+                position: 4,
+                letters: "ie".to_string()
+            }]
+        );
+        assert_eq!(
+            edit_path("bycyclee", "bicycle"),
+            vec![
+                Edit::Replace {
+                    position: 2,
+                    from: 'y',
+                    to: 'i'
+                },
+                Edit::Delete {
+                    position: 7,
+                    letter: 'e'
+                }
+            ]
+        );
+        assert_eq!(edit_path("dont", "don't").len(), 1);
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut stava = Stava::default();
+        stava.learn("spelling spelling spewing");
+
+        let actual = stava.explain("speling").unwrap();
+
+        assert_eq!(actual.input, "speling");
+        assert_eq!(actual.result.word, "spelling");
+        assert_eq!(actual.rule, Rule::MostFrequent);
+        assert_eq!(actual.distance, 1);
+        assert_eq!(
+            actual.candidates,
+            vec![
+                Candidate {
+                    word: "spelling".to_string(),
+                    count: 2,
+                    priority: 0,
+                    distance: 1
+                },
+                Candidate {
+                    word: "spewing".to_string(),
+                    count: 1,
+                    priority: 0,
+                    distance: 1
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_rules() {
+        let mut stava = Stava::default();
+        stava.learn("spelling don't");
+
+        assert_eq!(stava.explain("spelling").unwrap().rule, Rule::Known);
+        assert_eq!(stava.explain("dont").unwrap().rule, Rule::MissingApostrophe);
+        assert_eq!(stava.explain("xyzzy").unwrap().rule, Rule::NoCandidate);
+
+        stava.learn("knowledge");
+        let actual = stava.explain("nollij").unwrap();
+        assert_eq!(actual.rule, Rule::SoundAlike);
+        assert_eq!(actual.candidates[0].distance, 6);
+
+        let mut stava = Stava::builder().min_confidence(0.6).build().unwrap();
+        stava.learn("spelling");
+        let actual = stava.explain("speliing").unwrap();
+        assert_eq!(actual.rule, Rule::LowConfidence);
+        assert!(!actual.result.was_corrected);
+        assert_eq!(actual.candidates.len(), 1);
+
+        assert!(matches!(
+            stava.explain("héllo"),
+            Err(StavaError::InvalidWord(_))
+        ));
+    }
+}
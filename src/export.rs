@@ -0,0 +1,159 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::Stava;
+
+/// The file formats learned words can be exported to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    // A Hunspell `.dic` file without affix flags
+    Hunspell,
+    // An aspell personal word list, like `~/.aspell.en.pws`
+    AspellPws,
+    // A Vim spell file, like `~/.vim/spell/en.utf-8.add`
+    VimAdd,
+    // `word<TAB>count` lines, which can be learned again with `--frequency-list`
+    Tsv,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hunspell" => Ok(ExportFormat::Hunspell),
+            "aspell-pws" => Ok(ExportFormat::AspellPws),
+            "vim-add" => Ok(ExportFormat::VimAdd),
+            "tsv" => Ok(ExportFormat::Tsv),
+            _ => Err(format!("Unsupported export format [{}]", s)),
+        }
+    }
+}
+
+/// The order of the exported words
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SortOrder {
+    // The most frequent words first, words with the same count in alphabetical order
+    #[default]
+    Frequency,
+    Alphabet,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "frequency" => Ok(SortOrder::Frequency),
+            "alphabet" => Ok(SortOrder::Alphabet),
+            _ => Err(format!("Unsupported sort order [{}]", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExportOptions {
+    // Words counted fewer times than this are not exported
+    pub min_count: u32,
+    pub sort: SortOrder,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            min_count: 1,
+            sort: SortOrder::default(),
+        }
+    }
+}
+
+impl Stava {
+    /// Write the learned words to `writer` in `format`. Forbidden words are not exported.
+    pub fn export<W: Write>(
+        &self,
+        mut writer: W,
+        format: ExportFormat,
+        options: &ExportOptions,
+    ) -> io::Result<()> {
+        let mut words: Vec<(&str, u32)> = self
+            .words()
+            .filter(|&(word, count)| count >= options.min_count && !self.forbidden.contains(word))
+            .collect();
+        match options.sort {
+            SortOrder::Frequency => words.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0))),
+            SortOrder::Alphabet => words.sort_unstable(),
+        }
+
+        match format {
+            ExportFormat::Hunspell => writeln!(writer, "{}", words.len())?,
+            ExportFormat::AspellPws => {
+                writeln!(writer, "personal_ws-1.1 {} {}", self.language, words.len())?
+            }
+            ExportFormat::VimAdd | ExportFormat::Tsv => {}
+        }
+        for (word, count) in words {
+            match format {
+                // A `/` starts the affix flags in Hunspell dictionaries
+                ExportFormat::Hunspell => writeln!(writer, "{}", word.replace('/', "\\/"))?,
+                ExportFormat::AspellPws | ExportFormat::VimAdd => writeln!(writer, "{}", word)?,
+                ExportFormat::Tsv => writeln!(writer, "{}\t{}", word, count)?,
+            }
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(stava: &Stava, format: ExportFormat, options: &ExportOptions) -> String {
+        let mut output = Vec::new();
+        stava.export(&mut output, format, options).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn stava() -> Stava {
+        let mut stava = Stava::default();
+        stava.learn("the cat and the dog and the bird");
+        stava.forbidden.insert("dog", None);
+        stava
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let stava = stava();
+        let options = ExportOptions::default();
+
+        assert_eq!(
+            export(&stava, ExportFormat::Tsv, &options),
+            "the\t3\nand\t2\nbird\t1\ncat\t1\n"
+        );
+        assert_eq!(
+            export(&stava, ExportFormat::Hunspell, &options),
+            "4\nthe\nand\nbird\ncat\n"
+        );
+        assert_eq!(
+            export(&stava, ExportFormat::AspellPws, &options),
+            "personal_ws-1.1 en 4\nthe\nand\nbird\ncat\n"
+        );
+        assert_eq!(
+            export(&stava, ExportFormat::VimAdd, &options),
+            "the\nand\nbird\ncat\n"
+        );
+    }
+
+    #[test]
+    fn test_export_with_options() {
+        let stava = stava();
+        let options = ExportOptions {
+            min_count: 2,
+            sort: SortOrder::Alphabet,
+        };
+
+        assert_eq!(
+            export(&stava, ExportFormat::Tsv, &options),
+            "and\t2\nthe\t3\n"
+        );
+    }
+}
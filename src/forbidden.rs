@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::Result;
+use crate::normalize;
+use crate::replacements;
+
+/// Words that are never accepted or suggested, even if they are learned.
+///
+/// The words are read from text files with one `word` or `word -> preferred` per line, where the
+/// preferred word is suggested instead. Empty lines and lines starting with `#` are skipped.
+#[derive(Debug, Default, PartialEq)]
+pub struct ForbiddenWords {
+    words: BTreeMap<String, Option<String>>,
+}
+
+impl ForbiddenWords {
+    /// Read the words from `path`
+    pub fn load(path: &Path) -> Result<ForbiddenWords> {
+        let mut result = ForbiddenWords::default();
+        for (_, word, preferred) in replacements::read_lines(path)? {
+            result.insert(&word, preferred.as_deref());
+        }
+        Ok(result)
+    }
+
+    /// Forbid `word`, suggesting `preferred` instead if given
+    pub fn insert(&mut self, word: &str, preferred: Option<&str>) {
+        self.words.insert(
+            normalize(&word.to_lowercase()),
+            preferred.map(str::to_string),
+        );
+    }
+
+    /// Add all words in `other`, overwriting the preferred words of the same words
+    pub fn extend(&mut self, other: ForbiddenWords) {
+        self.words.extend(other.words);
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains_key(&normalize(&word.to_lowercase()))
+    }
+
+    /// The word suggested instead of a forbidden word
+    pub fn preferred(&self, word: &str) -> Option<&str> {
+        self.words
+            .get(&normalize(&word.to_lowercase()))
+            .and_then(Option::as_deref)
+    }
+
+    /// The forbidden words with their preferred words, in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.words
+            .iter()
+            .map(|(word, preferred)| (word.as_str(), preferred.as_deref()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("forbidden.txt");
+        fs::write(&path, "# Never use these\nalot -> a lot\nStavaCloud\n").unwrap();
+
+        let actual = ForbiddenWords::load(&path).unwrap();
+
+        assert_eq!(
+            actual.iter().collect::<Vec<_>>(),
+            vec![("alot", Some("a lot")), ("stavacloud", None)]
+        );
+        assert!(actual.contains("Alot"));
+        assert_eq!(actual.preferred("alot"), Some("a lot"));
+        assert_eq!(actual.preferred("stavacloud"), None);
+    }
+}
@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Result, StavaError};
+use crate::normalize;
+
+// The word and its count are separated by any of these
+const SEPARATORS: [char; 3] = ['\t', ',', ' '];
+
+/// How the counts of a frequency list are adjusted when it is loaded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrequencyOptions {
+    // Words counted fewer times than this are skipped
+    pub min_count: u64,
+    // Every count is multiplied by this, after skipping the rare words
+    pub scale: f64,
+}
+
+impl Default for FrequencyOptions {
+    fn default() -> Self {
+        FrequencyOptions {
+            min_count: 1,
+            scale: 1.0,
+        }
+    }
+}
+
+/// Words with their frequency counts, learned as if each word occurred that many times.
+///
+/// The list is read from text files with one `word<TAB>count`, `word,count` or `word count` per
+/// line, like the lists derived from Google n-grams or Wikipedia. Empty lines, lines starting with
+/// `#` and a header line like `word,count` are skipped.
+#[derive(Debug, Default, PartialEq)]
+pub struct FrequencyList {
+    counts: Vec<(String, u32)>,
+}
+
+impl FrequencyList {
+    /// Read the list from `path`
+    pub fn load(path: &Path, options: &FrequencyOptions) -> Result<FrequencyList> {
+        let contents = fs::read_to_string(path).map_err(|err| StavaError::io(path, err))?;
+        let mut result = FrequencyList::default();
+        let mut is_first_line = true;
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = parse_line(line);
+            if entry.is_none() && is_first_line {
+                is_first_line = false;
+                continue;
+            }
+            is_first_line = false;
+            let (word, count) = entry.ok_or_else(|| StavaError::InvalidFile {
+                path: path.to_path_buf(),
+                line: index + 1,
+                message: "Expected a line like `word<TAB>count`".to_string(),
+            })?;
+
+            if count >= options.min_count {
+                result.insert(word, scale(count, options.scale));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Add `count` occurrences of `word`
+    pub fn insert(&mut self, word: &str, count: u32) {
+        self.counts.push((normalize(&word.to_lowercase()), count));
+    }
+
+    /// The words with their counts, in the order they were read
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts
+            .iter()
+            .map(|(word, count)| (word.as_str(), *count))
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+// The word and count of a line, split at the last separator
+fn parse_line(line: &str) -> Option<(&str, u64)> {
+    let (word, count) = line.rsplit_once(SEPARATORS)?;
+    let word = word.trim_end_matches(SEPARATORS);
+    let count = count.parse().ok()?;
+    match word.is_empty() {
+        true => None,
+        false => Some((word, count)),
+    }
+}
+
+// Scaled counts are rounded, but never below 1 so that the word is still learned
+fn scale(count: u64, scale: f64) -> u32 {
+    let scaled = (count as f64 * scale).round();
+    scaled.clamp(1.0, f64::from(u32::MAX)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(contents: &str, options: &FrequencyOptions) -> Result<FrequencyList> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frequencies.txt");
+        fs::write(&path, contents).unwrap();
+        FrequencyList::load(&path, options)
+    }
+
+    #[test]
+    fn test_load() {
+        let contents = "# From a corpus\nthe\t2000\nOf 1500\n\nand,900\ndon\u{2019}t  30\n";
+
+        let actual = load(contents, &FrequencyOptions::default()).unwrap();
+
+        assert_eq!(
+            actual.iter().collect::<Vec<_>>(),
+            vec![("the", 2000), ("of", 1500), ("and", 900), ("don't", 30)]
+        );
+    }
+
+    #[test]
+    fn test_load_skips_header() {
+        let actual = load("word,count\nthe,10\n", &FrequencyOptions::default()).unwrap();
+
+        assert_eq!(actual.iter().collect::<Vec<_>>(), vec![("the", 10)]);
+    }
+
+    #[test]
+    fn test_load_with_options() {
+        let options = FrequencyOptions {
+            min_count: 10,
+            scale: 0.01,
+        };
+
+        let actual = load("the 2000\nof 160\nrare 9\nuncommon 10\n", &options).unwrap();
+
+        assert_eq!(
+            actual.iter().collect::<Vec<_>>(),
+            vec![("the", 20), ("of", 2), ("uncommon", 1)]
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_line() {
+        let actual = load("the\t10\nof\tmany\n", &FrequencyOptions::default()).unwrap_err();
+
+        assert!(matches!(actual, StavaError::InvalidFile { line: 2, .. }));
+    }
+}
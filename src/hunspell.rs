@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, StavaError};
+use crate::normalize;
+
+/// The words of a Hunspell dictionary with the affix rules that inflect them.
+///
+/// Only the stems in the `.dic` file are learned. The prefix and suffix rules of the `.aff` file
+/// are applied when a word is looked up, so "walked" is known if the stem "walk" has a flag of a
+/// rule adding "ed". Compounding and other options of the `.aff` file are not supported.
+#[derive(Debug, Default)]
+pub struct Hunspell {
+    // The flags of each stem, lowercased
+    stems: HashMap<String, HashSet<String>>,
+    // Prefix rules by the text they add
+    prefixes: HashMap<String, Vec<Affix>>,
+    // Suffix rules by the text they add
+    suffixes: HashMap<String, Vec<Affix>>,
+}
+
+#[derive(Debug)]
+struct Affix {
+    flag: String,
+    // If the rule can be combined with a rule of the other kind
+    cross_product: bool,
+    // The text removed from the stem before `add` is added
+    strip: String,
+    // What the stem has to start (prefix) or end (suffix) with for the rule to apply
+    condition: Vec<Condition>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Condition {
+    Any,
+    Char(char),
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FlagType {
+    // A single character per flag (default)
+    Char,
+    // Two characters per flag
+    Long,
+    // Comma-separated numbers
+    Num,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+impl Hunspell {
+    /// Read the dictionary at `dic` and the affix rules at the `.aff` file next to it
+    pub fn load(dic: &Path) -> Result<Hunspell> {
+        Hunspell::load_with_affixes(dic, &dic.with_extension("aff"))
+    }
+
+    pub fn load_with_affixes(dic: &Path, aff: &Path) -> Result<Hunspell> {
+        let mut result = Hunspell::default();
+        let flag_type = result.read_affixes(aff)?;
+        result.read_stems(dic, flag_type)?;
+        Ok(result)
+    }
+
+    /// The stems of the dictionary, in no particular order
+    pub fn stems(&self) -> impl Iterator<Item = &str> {
+        self.stems.keys().map(String::as_str)
+    }
+
+    // The stems `word` is an inflection of, by applying the affix rules in reverse
+    pub(crate) fn stems_of(&self, word: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        for (stem, suffix) in self.strip_affixes(word, AffixKind::Suffix) {
+            if self.has_flag(&stem, &suffix.flag) {
+                result.push(stem);
+            }
+        }
+        for (rest, prefix) in self.strip_affixes(word, AffixKind::Prefix) {
+            if self.has_flag(&rest, &prefix.flag) {
+                result.push(rest.clone());
+            }
+            if !prefix.cross_product {
+                continue;
+            }
+            for (stem, suffix) in self.strip_affixes(&rest, AffixKind::Suffix) {
+                if suffix.cross_product
+                    && self.has_flag(&stem, &prefix.flag)
+                    && self.has_flag(&stem, &suffix.flag)
+                {
+                    result.push(stem);
+                }
+            }
+        }
+        result
+    }
+
+    fn has_flag(&self, stem: &str, flag: &str) -> bool {
+        self.stems
+            .get(stem)
+            .is_some_and(|flags| flags.contains(flag))
+    }
+
+    // The stems that `word` could be made from by one of the rules, with the rule
+    fn strip_affixes(&self, word: &str, kind: AffixKind) -> Vec<(String, &Affix)> {
+        let mut result = Vec::new();
+        let boundaries = word
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([word.len()]);
+        for index in boundaries {
+            let (affixes, rest) = match kind {
+                AffixKind::Prefix => (self.prefixes.get(&word[..index]), &word[index..]),
+                AffixKind::Suffix => (self.suffixes.get(&word[index..]), &word[..index]),
+            };
+            for affix in affixes.into_iter().flatten() {
+                let stem = match kind {
+                    AffixKind::Prefix => format!("{}{}", affix.strip, rest),
+                    AffixKind::Suffix => format!("{}{}", rest, affix.strip),
+                };
+                if !stem.is_empty() && matches_condition(&stem, &affix.condition, kind) {
+                    result.push((stem, affix));
+                }
+            }
+        }
+        result
+    }
+
+    fn read_affixes(&mut self, path: &Path) -> Result<FlagType> {
+        let mut flag_type = FlagType::Char;
+        // The flag, kind and cross product of the rules being read
+        let mut current: Option<(String, AffixKind, bool)> = None;
+
+        let contents = read_file(path)?;
+        for (line, fields) in fields(&contents) {
+            let invalid = |message: &str| invalid_file(path, line, message);
+            match fields[0] {
+                "FLAG" => {
+                    flag_type = match fields.get(1).copied() {
+                        Some("long") => FlagType::Long,
+                        Some("num") => FlagType::Num,
+                        Some("UTF-8") => FlagType::Char,
+                        _ => return Err(invalid("Unsupported FLAG type")),
+                    }
+                }
+                "PFX" | "SFX" => {
+                    let kind = match fields[0] {
+                        "PFX" => AffixKind::Prefix,
+                        _ => AffixKind::Suffix,
+                    };
+                    if fields.len() < 4 {
+                        return Err(invalid(
+                            "Expected a line like `SFX flag strip add condition`",
+                        ));
+                    }
+                    // A header like `SFX D Y 4`
+                    if fields.len() == 4
+                        && matches!(fields[2], "Y" | "N")
+                        && fields[3].parse::<usize>().is_ok()
+                    {
+                        current = Some((fields[1].to_string(), kind, fields[2] == "Y"));
+                        continue;
+                    }
+
+                    // A rule like `SFX D y ied [^aeiou]y`
+                    let (flag, cross_product) = match &current {
+                        Some((flag, current_kind, cross_product))
+                            if *current_kind == kind && flag == fields[1] =>
+                        {
+                            (flag.clone(), *cross_product)
+                        }
+                        _ => return Err(invalid("Affix rule without a header")),
+                    };
+                    let strip = affix_text(fields[2]);
+                    let add = affix_text(fields[3].split('/').next().unwrap_or_default());
+                    let condition = parse_condition(fields.get(4).copied().unwrap_or("."))
+                        .ok_or_else(|| invalid("Invalid condition"))?;
+                    let rules = match kind {
+                        AffixKind::Prefix => &mut self.prefixes,
+                        AffixKind::Suffix => &mut self.suffixes,
+                    };
+                    rules.entry(add).or_default().push(Affix {
+                        flag,
+                        cross_product,
+                        strip,
+                        condition,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(flag_type)
+    }
+
+    fn read_stems(&mut self, path: &Path, flag_type: FlagType) -> Result<()> {
+        let contents = read_file(path)?;
+        for (index, (_, fields)) in fields(&contents).enumerate() {
+            // The first line is the number of stems
+            if index == 0 && fields[0].parse::<usize>().is_ok() {
+                continue;
+            }
+            let (stem, flags) = fields[0].split_once('/').unwrap_or((fields[0], ""));
+            let stem = normalize(&stem.to_lowercase());
+            self.stems
+                .entry(stem)
+                .or_default()
+                .extend(parse_flags(flags, flag_type));
+        }
+        Ok(())
+    }
+}
+
+// Files in other encodings than UTF-8 are read with invalid characters replaced
+fn read_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).map_err(|err| StavaError::io(path, err))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// The line number and whitespace-separated fields of the lines that are not empty or comments
+fn fields(contents: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(|(_, fields)| !fields.is_empty() && !fields[0].starts_with('#'))
+}
+
+fn invalid_file(path: &Path, line: usize, message: &str) -> StavaError {
+    StavaError::InvalidFile {
+        path: PathBuf::from(path),
+        line,
+        message: message.to_string(),
+    }
+}
+
+// `0` means no text
+fn affix_text(text: &str) -> String {
+    match text {
+        "0" => String::new(),
+        text => normalize(&text.to_lowercase()),
+    }
+}
+
+fn parse_flags(flags: &str, flag_type: FlagType) -> Vec<String> {
+    match flag_type {
+        FlagType::Char => flags.chars().map(String::from).collect(),
+        FlagType::Long => {
+            let chars: Vec<char> = flags.chars().collect();
+            chars.chunks(2).map(|pair| pair.iter().collect()).collect()
+        }
+        FlagType::Num => flags
+            .split(',')
+            .filter(|flag| !flag.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+// A condition like `[^aeiou]y`, where `.` matches any letter
+fn parse_condition(condition: &str) -> Option<Vec<Condition>> {
+    if condition == "." {
+        return Some(Vec::new());
+    }
+    let mut result = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        result.push(match c {
+            '.' => Condition::Any,
+            '[' => {
+                let mut set: Vec<char> = Vec::new();
+                loop {
+                    match chars.next()? {
+                        ']' => break,
+                        c => set.extend(c.to_lowercase()),
+                    }
+                }
+                match set.first() {
+                    Some('^') => Condition::NoneOf(set[1..].to_vec()),
+                    _ => Condition::OneOf(set),
+                }
+            }
+            c => Condition::Char(c.to_lowercase().next().unwrap_or(c)),
+        });
+    }
+    Some(result)
+}
+
+// Prefix conditions match the start of the stem and suffix conditions the end of it
+fn matches_condition(stem: &str, condition: &[Condition], kind: AffixKind) -> bool {
+    let chars: Vec<char> = stem.chars().collect();
+    if chars.len() < condition.len() {
+        return false;
+    }
+    let start = match kind {
+        AffixKind::Prefix => 0,
+        AffixKind::Suffix => chars.len() - condition.len(),
+    };
+    chars[start..]
+        .iter()
+        .zip(condition)
+        .all(|(c, condition)| match condition {
+            Condition::Any => true,
+            Condition::Char(expected) => c == expected,
+            Condition::OneOf(set) => set.contains(c),
+            Condition::NoneOf(set) => !set.contains(c),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Hunspell {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hunspell");
+        Hunspell::load(&dir.join("en.dic")).unwrap()
+    }
+
+    fn stems_of(hunspell: &Hunspell, word: &str) -> Vec<String> {
+        let mut stems = hunspell.stems_of(word);
+        stems.sort();
+        stems.dedup();
+        stems
+    }
+
+    #[test]
+    fn test_load() {
+        let hunspell = fixture();
+
+        let mut stems: Vec<&str> = hunspell.stems().collect();
+        stems.sort_unstable();
+        assert_eq!(stems, vec!["cry", "do", "happy", "paris", "walk"]);
+    }
+
+    #[test]
+    fn test_stems_of() {
+        let hunspell = fixture();
+
+        assert_eq!(stems_of(&hunspell, "walked"), vec!["walk"]);
+        assert_eq!(stems_of(&hunspell, "walking"), vec!["walk"]);
+        assert_eq!(stems_of(&hunspell, "cried"), vec!["cry"]);
+        assert_eq!(stems_of(&hunspell, "undo"), vec!["do"]);
+        assert_eq!(stems_of(&hunspell, "unhappiness"), vec!["happy"]);
+        assert_eq!(stems_of(&hunspell, "redo"), Vec::<String>::new());
+        // The condition `[^y]` does not match "cry"
+        assert_eq!(stems_of(&hunspell, "cryed"), Vec::<String>::new());
+        // "paris" has no flags
+        assert_eq!(stems_of(&hunspell, "parises"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_parse_flags() {
+        assert_eq!(parse_flags("DG", FlagType::Char), vec!["D", "G"]);
+        assert_eq!(parse_flags("AaBb", FlagType::Long), vec!["Aa", "Bb"]);
+        assert_eq!(parse_flags("1,20", FlagType::Num), vec!["1", "20"]);
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        assert_eq!(
+            parse_condition("[^aeiou]y"),
+            Some(vec![
+                Condition::NoneOf(vec!['a', 'e', 'i', 'o', 'u']),
+                Condition::Char('y')
+            ])
+        );
+        assert_eq!(parse_condition("."), Some(vec![]));
+        assert_eq!(parse_condition("[ab"), None);
+    }
+}
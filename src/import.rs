@@ -0,0 +1,206 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Result, StavaError};
+use crate::personal::PersonalDictionary;
+use crate::Stava;
+
+/// The personal word list formats of other spell checkers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportFormat {
+    // An aspell personal word list, like `~/.aspell.en.pws`
+    AspellPws,
+    // A Hunspell personal dictionary, like `~/.hunspell_en_US`
+    Hunspell,
+    // A Vim spell file, like `~/.vim/spell/en.utf-8.add`
+    VimAdd,
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "aspell-pws" => Ok(ImportFormat::AspellPws),
+            "hunspell" => Ok(ImportFormat::Hunspell),
+            "vim-add" => Ok(ImportFormat::VimAdd),
+            _ => Err(format!("Unsupported import format [{}]", s)),
+        }
+    }
+}
+
+impl ImportFormat {
+    /// The format of a file by its extension: `.pws` for aspell, `.add` for Vim and Hunspell for
+    /// anything else
+    pub fn detect(path: &Path) -> ImportFormat {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("pws") => ImportFormat::AspellPws,
+            Some("add") => ImportFormat::VimAdd,
+            _ => ImportFormat::Hunspell,
+        }
+    }
+}
+
+/// The words of a personal word list, with the words it marks as misspelled.
+///
+/// Bad words are marked with `/!` in Vim spell files and with a leading `*` in Hunspell personal
+/// dictionaries.
+#[derive(Debug, Default, PartialEq)]
+pub struct WordList {
+    words: Vec<String>,
+    bad_words: Vec<String>,
+}
+
+impl WordList {
+    /// Read the list from `path`
+    pub fn load(path: &Path, format: ImportFormat) -> Result<WordList> {
+        let contents = fs::read_to_string(path).map_err(|err| StavaError::io(path, err))?;
+        let mut result = WordList::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            let is_header = index == 0 && line.starts_with("personal_ws-");
+            if line.is_empty() || is_header || line.starts_with('#') {
+                continue;
+            }
+
+            match format {
+                ImportFormat::AspellPws => result.words.push(line.to_string()),
+                ImportFormat::Hunspell => {
+                    // A word may be followed by `/` and an example word with the same affixes
+                    let (word, _) = line.split_once('/').unwrap_or((line, ""));
+                    match word.strip_prefix('*') {
+                        Some(bad_word) => result.bad_words.push(bad_word.to_string()),
+                        None => result.words.push(word.to_string()),
+                    }
+                }
+                ImportFormat::VimAdd => {
+                    // Flags like `=` (keep case) and `!` (bad word) follow a `/`
+                    let (word, flags) = line.split_once('/').unwrap_or((line, ""));
+                    match flags.contains('!') {
+                        true => result.bad_words.push(word.to_string()),
+                        false => result.words.push(word.to_string()),
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// The accepted words, in the order they were read
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    /// The words marked as misspelled, in the order they were read
+    pub fn bad_words(&self) -> impl Iterator<Item = &str> {
+        self.bad_words.iter().map(String::as_str)
+    }
+}
+
+impl PersonalDictionary {
+    /// Add the words of `list` to the dictionary and its bad words to the bad words
+    pub fn import(&mut self, list: &WordList) {
+        for word in list.words() {
+            self.add(word);
+        }
+        for word in list.bad_words() {
+            self.add_bad_word(word);
+        }
+    }
+}
+
+impl Stava {
+    /// Learn the words of `list` and never accept or suggest its bad words
+    pub fn import(&mut self, list: &WordList) {
+        for word in list.words() {
+            self.learn(word);
+        }
+        for word in list.bad_words() {
+            self.forbidden.insert(word, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(contents: &str, format: ImportFormat) -> WordList {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words");
+        fs::write(&path, contents).unwrap();
+        WordList::load(&path, format).unwrap()
+    }
+
+    #[test]
+    fn test_load_aspell_pws() {
+        let actual = load(
+            "personal_ws-1.1 en 2\nKubernetes\nrustacean\n",
+            ImportFormat::AspellPws,
+        );
+
+        assert_eq!(
+            actual.words().collect::<Vec<_>>(),
+            vec!["Kubernetes", "rustacean"]
+        );
+        assert_eq!(actual.bad_words().count(), 0);
+    }
+
+    #[test]
+    fn test_load_hunspell() {
+        let actual = load("rustacean\nfoobar/bar\n*alot\n", ImportFormat::Hunspell);
+
+        assert_eq!(
+            actual.words().collect::<Vec<_>>(),
+            vec!["rustacean", "foobar"]
+        );
+        assert_eq!(actual.bad_words().collect::<Vec<_>>(), vec!["alot"]);
+    }
+
+    #[test]
+    fn test_load_vim_add() {
+        let actual = load(
+            "rustacean\nNASA/=\nalot/!\n#removed\n",
+            ImportFormat::VimAdd,
+        );
+
+        assert_eq!(
+            actual.words().collect::<Vec<_>>(),
+            vec!["rustacean", "NASA"]
+        );
+        assert_eq!(actual.bad_words().collect::<Vec<_>>(), vec!["alot"]);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            ImportFormat::detect(Path::new(".aspell.en.pws")),
+            ImportFormat::AspellPws
+        );
+        assert_eq!(
+            ImportFormat::detect(Path::new("en.utf-8.add")),
+            ImportFormat::VimAdd
+        );
+        assert_eq!(
+            ImportFormat::detect(Path::new(".hunspell_en_US")),
+            ImportFormat::Hunspell
+        );
+    }
+
+    #[test]
+    fn test_import() {
+        let list = load("rustacean\nalot/!\n", ImportFormat::VimAdd);
+
+        let mut dict = PersonalDictionary::default();
+        dict.import(&list);
+        assert_eq!(dict.words().collect::<Vec<_>>(), vec!["rustacean"]);
+        assert_eq!(dict.bad_words().collect::<Vec<_>>(), vec!["alot"]);
+
+        let mut stava = Stava::default();
+        stava.learn("a lot alot");
+        stava.import(&list);
+        assert!(stava.contains("rustacean"));
+        assert_eq!(stava.check("alot").len(), 1);
+    }
+}
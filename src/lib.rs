@@ -1,98 +1,850 @@
 #[macro_use]
 extern crate lazy_static;
 extern crate regex;
-#[cfg(test)]
 #[macro_use]
 extern crate include_dir;
 
-use regex::Regex;
+use include_dir::Dir;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, BufRead};
 use std::iter::FromIterator;
+use std::path::Path;
+use std::str::FromStr;
 
-lazy_static! {
-    static ref ENG_ALPHABET: Vec<&'static str> = "abcdefghijklmnopqrstuvwxyz"
-        .split("")
-        .filter(|&l| !l.is_empty())
-        .collect();
-}
+mod builder;
+pub mod config;
+pub mod dictionary;
+pub mod diff;
+pub mod error;
+pub mod eval;
+pub mod explain;
+pub mod export;
+pub mod forbidden;
+pub mod frequency;
+pub mod hunspell;
+pub mod import;
+pub mod merge;
+pub mod personal;
+pub mod phonetic;
+pub mod replacements;
+mod segment;
+pub mod stats;
+pub mod tokenizer;
+mod unlearn;
+pub mod walk;
+
+pub use builder::StavaBuilder;
+pub use error::StavaError;
+
+use dictionary::{Dictionary, DEFAULT_DICTIONARY_NAME, PERSONAL_DICTIONARY_NAME};
+use error::Result;
+use explain::{note_rule, Candidate, Rule, Trace};
+use forbidden::ForbiddenWords;
+use frequency::FrequencyList;
+use hunspell::Hunspell;
+use personal::PersonalDictionary;
+use replacements::Replacements;
+use tokenizer::{AsciiTokenizer, Tokenizer};
+
+static ASSETS_DIR: Dir<'_> = include_dir!("src/assets");
+
+const ENG_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+const DEFAULT_MAX_DISTANCE: usize = 2;
+// A candidate with one more edit must be this many times as frequent to be ranked the same
+const DEFAULT_EDIT_PENALTY: f64 = 100.0;
+// Text without whitespace longer than this is split between words when streaming
+const MAX_PENDING_LEN: usize = 1024 * 1024;
 
 pub struct Stava {
     // The words from the input with the frequency count for each word
-    pub words_w_count: HashMap<String, u32>,
+    words_w_count: HashMap<String, u32>,
+    // The sum of the frequency counts of all words
+    total_count: u64,
+    // The named dictionaries words have been learned from
+    dictionaries: Vec<Dictionary>,
+    // The index of the dictionary each word is reported as coming from
+    origins: HashMap<String, usize>,
+    // Hunspell dictionaries whose affix rules inflect the learned stems
+    hunspell: Vec<Hunspell>,
+    // The learned words by their phonetic code, if words are matched by how they sound
+    phonetic_index: Option<HashMap<String, Vec<String>>>,
+    // Words that are never suggested as corrections
+    ignored: HashSet<String>,
+    // Words that are always corrected to their replacement
+    replacements: Replacements,
+    // Words that are never accepted or suggested
+    forbidden: ForbiddenWords,
+    // The language of the learned words
+    language: Language,
+    // Finds the words in lowercased text
+    tokenizer: Box<dyn Tokenizer>,
+    // The letters used when editing words
+    alphabet: Vec<String>,
+    // The maximum number of edits between a word and its correction
+    max_distance: usize,
+    // How the correction is chosen among the candidates
+    ranking: Ranking,
+    // Corrections less confident than this are not made
+    min_confidence: f64,
+}
+
+impl Default for Stava {
+    fn default() -> Self {
+        Stava {
+            words_w_count: HashMap::new(),
+            total_count: 0,
+            dictionaries: Vec::new(),
+            origins: HashMap::new(),
+            hunspell: Vec::new(),
+            phonetic_index: Some(HashMap::new()),
+            ignored: HashSet::new(),
+            replacements: Replacements::default(),
+            forbidden: ForbiddenWords::default(),
+            language: Language::English,
+            tokenizer: Box::new(AsciiTokenizer),
+            alphabet: ENG_ALPHABET.chars().map(String::from).collect(),
+            max_distance: DEFAULT_MAX_DISTANCE,
+            ranking: Ranking::default(),
+            min_confidence: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+#[non_exhaustive]
+pub enum Ranking {
+    // Peter Norvig's strategy: the most frequent of the candidates with the fewest edits
+    #[default]
+    Norvig,
+    // The candidates of all distances are ranked together by their count divided by
+    // `edit_penalty` for each edit, so a common word can win over a rare one with fewer edits
+    Combined {
+        edit_penalty: f64,
+    },
+}
+
+impl Ranking {
+    /// The combined ranking with the default edit penalty
+    pub fn combined() -> Ranking {
+        Ranking::Combined {
+            edit_penalty: DEFAULT_EDIT_PENALTY,
+        }
+    }
+}
+
+impl FromStr for Ranking {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "norvig" => Ok(Ranking::Norvig),
+            "combined" => Ok(Ranking::combined()),
+            _ => Err(format!("Unsupported ranking [{}]", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for Ranking {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+// A learned word that may be the correction
+struct RankedCandidate<'a> {
+    word: &'a str,
+    priority: i32,
+    count: u32,
+    // The number of edits from the misspelled word
+    distance: usize,
+    // How strongly the ranking prefers the word among the candidates with the same priority
+    weight: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            _ => Err(format!("Unsupported language [{}]", s)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+impl Language {
+    pub fn alphabet(&self) -> &'static str {
+        match self {
+            Language::English => ENG_ALPHABET,
+        }
+    }
+
+    // If words in the language can be matched by how they sound
+    pub fn has_phonetic_matching(&self) -> bool {
+        match self {
+            Language::English => true,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "en"),
+        }
+    }
+}
+
+pub fn default_words() -> Result<&'static str> {
+    asset("words.txt")
+}
+
+// Common English contractions, learned together with the default words
+fn contractions() -> Result<&'static str> {
+    asset("contractions.txt")
+}
+
+fn asset(name: &str) -> Result<&'static str> {
+    ASSETS_DIR
+        .get_file(name)
+        .and_then(|file| file.contents_utf8())
+        .ok_or(StavaError::MissingDefaultWords)
+}
+
+#[derive(Debug, PartialEq, Serialize)]
 pub struct StavaResult {
     // The corrected word. If it was not corrected then the input word is returned
     pub word: String,
     // If the word was corrected
     pub was_corrected: bool,
+    // The name of the dictionary the word was learned from, if it was learned from a named one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<String>,
+    // How sure the correction is, from 0 to 1. Known words and words corrected by a replacement
+    // are 1 and words that were not corrected are 0.
+    pub confidence: f64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Misspelling {
+    // The line the word is on, starting at 1
+    pub line: usize,
+    // The column the word starts at, starting at 1
+    pub column: usize,
+    // The misspelled word as it is written in the text
+    pub word: String,
+    // The corrected word, if a correction was found
+    pub suggestion: Option<String>,
+    // The name of the dictionary the suggestion was learned from, if it was learned from a named one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<String>,
 }
 
 impl Stava {
+    pub fn builder() -> StavaBuilder {
+        StavaBuilder::new()
+    }
+
+    // Frequency count of a learned word. Words inflected by Hunspell affix rules have the count of
+    // their stem.
+    pub fn count(&self, word: &str) -> Option<u32> {
+        if let Some(&count) = self.words_w_count.get(word) {
+            return Some(count);
+        }
+        self.hunspell
+            .iter()
+            .flat_map(|hunspell| hunspell.stems_of(word))
+            .filter_map(|stem| self.words_w_count.get(&stem).copied())
+            .max()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.count(word).is_some()
+    }
+
+    // The learned words with their frequency count, in no particular order
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words_w_count
+            .iter()
+            .map(|(word, count)| (word.as_str(), *count))
+    }
+
+    // Number of unique learned words
+    pub fn len(&self) -> usize {
+        self.words_w_count.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words_w_count.is_empty()
+    }
+
+    // The named dictionaries words have been learned from, in the order they were first used
+    pub fn dictionaries(&self) -> &[Dictionary] {
+        &self.dictionaries
+    }
+
+    /// The dictionary a learned word is reported as coming from.
+    ///
+    /// A word learned from several dictionaries comes from the one with the highest priority, or
+    /// the first one learned if their priorities are equal.
+    pub fn dictionary_of(&self, word: &str) -> Option<&Dictionary> {
+        let origin = self.origins.get(word).copied().or_else(|| {
+            self.hunspell
+                .iter()
+                .flat_map(|hunspell| hunspell.stems_of(word))
+                .find_map(|stem| self.origins.get(&stem).copied())
+        });
+        origin.map(|index| &self.dictionaries[index])
+    }
+
     pub fn learn(&mut self, text: &str) {
-        let re = Regex::new(r"[a-z]+").unwrap();
-        for m in re.find_iter(&text.to_lowercase()) {
-            let count = self
-                .words_w_count
-                .entry(m.as_str().to_string())
-                .or_insert(0);
-            *count += 1;
+        self.learn_words(text, None);
+    }
+
+    /// Learn the words in `text` as part of `dictionary`
+    pub fn learn_as(&mut self, dictionary: &Dictionary, text: &str) {
+        let index = self.dictionary_index(dictionary);
+        self.learn_words(text, Some(index));
+    }
+
+    fn learn_words(&mut self, text: &str, dictionary: Option<usize>) {
+        for (_, word) in self.tokenizer.tokenize(&text.to_lowercase()) {
+            self.add_count(normalize(word), 1, dictionary);
         }
     }
 
-    pub fn correct(&mut self, word: &str) -> StavaResult {
-        // Word is known so we return it
-        if self.words_w_count.contains_key(word) {
-            return StavaResult {
-                word: word.to_string(),
-                was_corrected: false,
-            };
+    // The index of the dictionary with the name of `dictionary`, which is added if it is new
+    fn dictionary_index(&mut self, dictionary: &Dictionary) -> usize {
+        match self
+            .dictionaries
+            .iter()
+            .position(|known| known.name == dictionary.name)
+        {
+            Some(index) => index,
+            None => {
+                self.dictionaries.push(dictionary.clone());
+                self.dictionaries.len() - 1
+            }
         }
+    }
+
+    pub fn learn_default_words(&mut self) -> Result<()> {
+        let dictionary = Dictionary::new(DEFAULT_DICTIONARY_NAME);
+        self.learn_as(&dictionary, default_words()?);
+        self.learn_as(&dictionary, contractions()?);
+        Ok(())
+    }
+
+    /// Learn the words read from `reader` without reading it all into memory.
+    ///
+    /// The text is learned in chunks ending at whitespace so words are never split. Invalid UTF-8
+    /// is replaced with U+FFFD.
+    pub fn learn_from_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        self.read_words(reader, None)
+    }
+
+    /// Learn the words read from `reader` as part of `dictionary`
+    pub fn learn_from_reader_as<R: BufRead>(
+        &mut self,
+        dictionary: &Dictionary,
+        reader: R,
+    ) -> io::Result<()> {
+        let index = self.dictionary_index(dictionary);
+        self.read_words(reader, Some(index))
+    }
+
+    fn read_words<R: BufRead>(&mut self, reader: R, dictionary: Option<usize>) -> io::Result<()> {
+        read_chunks(reader, |text| self.learn_words(text, dictionary))
+    }
+
+    // Binary files are skipped
+    pub fn learn_from_path(&mut self, path: &Path) -> Result<()> {
+        self.read_path(path, None)
+    }
+
+    /// Learn the words in the file at `path` as part of `dictionary`. Binary files are skipped.
+    pub fn learn_from_path_as(&mut self, dictionary: &Dictionary, path: &Path) -> Result<()> {
+        let index = self.dictionary_index(dictionary);
+        self.read_path(path, Some(index))
+    }
 
-        let mut candidates: HashMap<u32, String> = HashMap::new();
-        let edits = self.get_edits(word);
+    fn read_path(&mut self, path: &Path, dictionary: Option<usize>) -> Result<()> {
+        if let Some(reader) = walk::open_text_file(path)? {
+            self.read_words(reader, dictionary)
+                .map_err(|err| StavaError::io(path, err))?;
+        }
+        Ok(())
+    }
 
-        // Add edited words as candidates
-        for edit in &edits {
-            if let Some(count) = self.words_w_count.get(edit) {
-                candidates.insert(*count, edit.to_string());
+    /// Learn the words in a frequency list, each as many times as its count
+    pub fn learn_frequencies(&mut self, list: &FrequencyList) {
+        for (word, count) in list.iter() {
+            self.add_count(word.to_string(), count, None);
+        }
+    }
+
+    /// Learn the words in a frequency list as part of `dictionary`
+    pub fn learn_frequencies_as(&mut self, dictionary: &Dictionary, list: &FrequencyList) {
+        let index = self.dictionary_index(dictionary);
+        for (word, count) in list.iter() {
+            self.add_count(word.to_string(), count, Some(index));
+        }
+    }
+
+    /// Learn the stems of a Hunspell dictionary and accept the words its affix rules make of them
+    pub fn learn_hunspell(&mut self, hunspell: Hunspell) {
+        self.add_hunspell(hunspell, None);
+    }
+
+    /// Learn the stems of a Hunspell dictionary as part of `dictionary`
+    pub fn learn_hunspell_as(&mut self, dictionary: &Dictionary, hunspell: Hunspell) {
+        let index = self.dictionary_index(dictionary);
+        self.add_hunspell(hunspell, Some(index));
+    }
+
+    fn add_hunspell(&mut self, hunspell: Hunspell, dictionary: Option<usize>) {
+        for stem in hunspell.stems() {
+            self.add_count(stem.to_string(), 1, dictionary);
+        }
+        self.hunspell.push(hunspell);
+    }
+
+    pub fn merge_personal(&mut self, dict: &PersonalDictionary) {
+        let index = self.dictionary_index(&Dictionary::new(PERSONAL_DICTIONARY_NAME));
+        for word in dict.words() {
+            self.add_count(normalize(word), 1, Some(index));
+        }
+        self.ignored.extend(dict.ignored().map(normalize));
+        for word in dict.bad_words() {
+            self.forbidden.insert(word, None);
+        }
+    }
+
+    // Each occurrence is counted as many times as the weight of the dictionary it is learned from
+    fn add_count(&mut self, word: String, count: u32, dictionary: Option<usize>) {
+        let count = match dictionary {
+            Some(index) => count.saturating_mul(self.dictionaries[index].weight),
+            None => count,
+        };
+        if let Some(index) = dictionary {
+            self.set_origin(&word, index);
+        }
+
+        let count = self.count_of(&word).saturating_add(count);
+        self.set_count(word, count);
+    }
+
+    // The word is reported as coming from the dictionary, unless it already comes from one with
+    // the same or a higher priority
+    fn set_origin(&mut self, word: &str, dictionary: usize) {
+        let priority = self.dictionaries[dictionary].priority;
+        match self.origins.get(word) {
+            Some(&origin) if self.dictionaries[origin].priority >= priority => {}
+            _ => {
+                self.origins.insert(word.to_string(), dictionary);
             }
         }
+    }
 
-        // Return candidate if found in edits
-        if let Some(candidate) = candidates.iter().max_by_key(|&entry| entry.0) {
-            return StavaResult {
-                word: candidate.1.to_string(),
-                was_corrected: true,
-            };
+    // The learned count of the word, without inflecting it with Hunspell affix rules
+    fn count_of(&self, word: &str) -> u32 {
+        self.words_w_count.get(word).copied().unwrap_or(0)
+    }
+
+    // Replaces the count of the word, keeping the total count and the phonetic index up to date.
+    // A word with a count of 0 is removed.
+    fn set_count(&mut self, word: String, count: u32) {
+        self.total_count = self.total_count - u64::from(self.count_of(&word)) + u64::from(count);
+        if count == 0 {
+            self.remove_word(&word);
+            return;
         }
+        match self.words_w_count.get_mut(&word) {
+            Some(word_count) => *word_count = count,
+            None => {
+                if let Some(index) = self.phonetic_index.as_mut() {
+                    index
+                        .entry(phonetic::metaphone(&word))
+                        .or_default()
+                        .push(word.clone());
+                }
+                self.words_w_count.insert(word, count);
+            }
+        }
+    }
 
-        // Add additional edits based on first edited words
-        for edit in &edits {
-            for word in self.get_edits(edit) {
-                if let Some(count) = self.words_w_count.get(&word) {
-                    candidates.insert(*count, word);
+    fn remove_word(&mut self, word: &str) {
+        if self.words_w_count.remove(word).is_none() {
+            return;
+        }
+        self.origins.remove(word);
+        if let Some(index) = self.phonetic_index.as_mut() {
+            let code = phonetic::metaphone(word);
+            if let Some(words) = index.get_mut(&code) {
+                words.retain(|known| known != word);
+                if words.is_empty() {
+                    index.remove(&code);
                 }
             }
         }
+    }
+
+    // Enables or disables matching words by how they sound
+    pub(crate) fn set_phonetic_matching(&mut self, enabled: bool) {
+        self.phonetic_index = if enabled {
+            let mut index: HashMap<String, Vec<String>> = HashMap::new();
+            for word in self.words_w_count.keys() {
+                index
+                    .entry(phonetic::metaphone(word))
+                    .or_default()
+                    .push(word.clone());
+            }
+            Some(index)
+        } else {
+            None
+        };
+    }
 
-        // Return candidate if found in edits
-        if let Some(candidate) = candidates.iter().max_by_key(|&entry| entry.0) {
+    pub fn correct(&self, word: &str) -> Result<StavaResult> {
+        let normalized = self.normalize_valid(word)?;
+        Ok(self.correct_valid(&normalized))
+    }
+
+    // The normalized word, if it only contains letters of the alphabet and apostrophes
+    pub(crate) fn normalize_valid(&self, word: &str) -> Result<String> {
+        let normalized = normalize(word);
+        let is_valid_letter =
+            |letter: char| letter == '\'' || self.alphabet.iter().any(|l| l.starts_with(letter));
+        if normalized.is_empty() || !normalized.chars().all(is_valid_letter) {
+            return Err(StavaError::InvalidWord(word.to_string()));
+        }
+        Ok(normalized)
+    }
+
+    /// Correct `words` in parallel. The results are in the same order as `words`.
+    #[cfg(feature = "parallel")]
+    pub fn correct_all<S: AsRef<str> + Sync>(&self, words: &[S]) -> Vec<Result<StavaResult>> {
+        words
+            .par_iter()
+            .map(|word| self.correct(word.as_ref()))
+            .collect()
+    }
+
+    // Correct a normalized word that is known to only contain valid letters
+    fn correct_valid(&self, word: &str) -> StavaResult {
+        self.correct_traced(word, None)
+    }
+
+    // Correct a valid word, recording how the correction was chosen in `trace` if it is given
+    pub(crate) fn correct_traced(&self, word: &str, mut trace: Option<&mut Trace>) -> StavaResult {
+        // Replacements take precedence, even over known words
+        if let Some(replacement) = self.replacements.get(word) {
+            note_rule(&mut trace, Rule::Replacement);
+            return StavaResult {
+                word: replacement.to_string(),
+                was_corrected: true,
+                dictionary: None,
+                confidence: 1.0,
+            };
+        }
+        if let Some(preferred) = self.forbidden.preferred(word) {
+            note_rule(&mut trace, Rule::Preferred);
             return StavaResult {
-                word: candidate.1.to_string(),
+                word: preferred.to_string(),
                 was_corrected: true,
+                dictionary: None,
+                confidence: 1.0,
             };
         }
 
+        // Word is known so we return it
+        if self.is_known_word(word) {
+            note_rule(&mut trace, Rule::Known);
+            return self.result(word, false, 1.0);
+        }
+
+        // A missing apostrophe is the most likely mistake, e.g. "dont" for "don't"
+        let apostrophe_edits = self.apostrophe_inserts(&self.splits(word));
+        let apostrophe_candidates = apostrophe_edits.iter().map(|edit| (edit.as_str(), 1));
+        // If it is not confident enough, the edits may still find a better correction
+        let apostrophe_result = match self.choose(word, apostrophe_candidates, trace.as_deref_mut())
+        {
+            Some(result) if result.was_corrected => {
+                note_rule(&mut trace, Rule::MissingApostrophe);
+                return result;
+            }
+            result => result,
+        };
+
+        let mut edits: HashSet<String> = HashSet::from_iter([word.to_string()]);
+        // The learned candidates of all distances with their fewest edits, if they are ranked
+        // together
+        let mut all_candidates: HashMap<String, usize> = HashMap::new();
+
+        for distance in 1..=self.max_distance {
+            // Add additional edits based on the previously edited words
+            edits = edits.iter().flat_map(|edit| self.get_edits(edit)).collect();
+
+            // Add edited words as candidates
+            let candidates: Vec<&str> = edits.iter().map(String::as_str).collect();
+
+            match self.ranking {
+                // Return candidate if found in edits
+                Ranking::Norvig => {
+                    let candidates = candidates.into_iter().map(|edit| (edit, distance));
+                    if let Some(result) = self.choose(word, candidates, trace.as_deref_mut()) {
+                        return result;
+                    }
+                }
+                Ranking::Combined { .. } => {
+                    for candidate in candidates {
+                        if self.rank(candidate).is_some() {
+                            all_candidates
+                                .entry(candidate.to_string())
+                                .or_insert(distance);
+                        }
+                    }
+                }
+            }
+        }
+
+        let candidates = all_candidates
+            .iter()
+            .map(|(candidate, &distance)| (candidate.as_str(), distance));
+        if let Some(result) = self.choose(word, candidates, trace.as_deref_mut()) {
+            return result;
+        }
+
+        // Words that sound the same are candidates when no edits are close enough, if their
+        // length is not too different
+        let word_len = word.chars().count();
+        let sound_alikes = self
+            .sound_alikes(word)
+            .iter()
+            .filter(|sound_alike| {
+                sound_alike.chars().count().abs_diff(word_len) <= self.max_distance + 2
+            })
+            .map(|sound_alike| {
+                let distance = explain::edit_path(word, sound_alike).len();
+                (sound_alike.as_str(), distance)
+            });
+        if let Some(result) = self.choose(word, sound_alikes, trace.as_deref_mut()) {
+            if result.was_corrected {
+                note_rule(&mut trace, Rule::SoundAlike);
+            }
+            return result;
+        }
+        if let Some(result) = apostrophe_result {
+            return result;
+        }
+
         // No correction was found
+        note_rule(&mut trace, Rule::NoCandidate);
+        self.uncorrected(word)
+    }
+
+    // The best ranked of the candidates, each with its number of edits from the word, or the word
+    // itself if that correction is not confident enough. None if no candidate is learned.
+    fn choose<'a>(
+        &self,
+        word: &str,
+        candidates: impl IntoIterator<Item = (&'a str, usize)>,
+        trace: Option<&mut Trace>,
+    ) -> Option<StavaResult> {
+        let mut ranked: Vec<RankedCandidate> = candidates
+            .into_iter()
+            .filter_map(|(candidate, distance)| {
+                let (priority, count) = self.rank(candidate)?;
+                Some(RankedCandidate {
+                    word: candidate,
+                    priority,
+                    count,
+                    distance,
+                    weight: self.weight(count, distance),
+                })
+            })
+            .collect();
+        ranked.sort_unstable_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(b.weight.total_cmp(&a.weight))
+                .then(a.word.cmp(b.word))
+        });
+        ranked.dedup_by_key(|candidate| candidate.word);
+
+        let best = ranked.first()?;
+        let runner_up = ranked.get(1);
+        let confidence = self.confidence(best, runner_up);
+        let is_confident = confidence >= self.min_confidence;
+
+        if let Some(trace) = trace {
+            trace.rule = Some(match runner_up {
+                _ if !is_confident => Rule::LowConfidence,
+                None => Rule::OnlyCandidate,
+                Some(runner_up) if best.priority > runner_up.priority => Rule::HighestPriority,
+                Some(runner_up) if best.weight > runner_up.weight => match self.ranking {
+                    Ranking::Norvig => Rule::MostFrequent,
+                    Ranking::Combined { .. } => Rule::HighestScore,
+                },
+                Some(_) => Rule::Alphabetical,
+            });
+            trace.candidates = ranked
+                .iter()
+                .map(|candidate| Candidate {
+                    word: candidate.word.to_string(),
+                    count: candidate.count,
+                    priority: candidate.priority,
+                    distance: candidate.distance,
+                })
+                .collect();
+        }
+
+        match is_confident {
+            true => Some(self.result(best.word, true, confidence)),
+            false => Some(self.uncorrected(word)),
+        }
+    }
+
+    // How strongly a candidate learned `count` times and `distance` edits from the word is
+    // preferred by the ranking, among candidates with the same priority
+    fn weight(&self, count: u32, distance: usize) -> f64 {
+        match self.ranking {
+            // All candidates have the same number of edits
+            Ranking::Norvig => f64::from(count),
+            Ranking::Combined { edit_penalty } => {
+                f64::from(count) / edit_penalty.powi(distance as i32)
+            }
+        }
+    }
+
+    // How sure the correction to the best candidate is, from 0 to 1. It is the product of how
+    // frequent the candidate is compared to the average learned word on a log scale, its share of
+    // the weights of the candidate and the runner-up, and the inverse of the number of edits.
+    fn confidence(&self, best: &RankedCandidate, runner_up: Option<&RankedCandidate>) -> f64 {
+        let count = f64::from(best.count);
+        let average = self.total_count as f64 / self.words_w_count.len().max(1) as f64;
+        let frequency = count.ln_1p() / (count.ln_1p() + average.ln_1p());
+        let margin = best.weight / (best.weight + runner_up.map_or(0.0, |other| other.weight));
+        let confidence = frequency * margin / best.distance.max(1) as f64;
+        (confidence * 1000.0).round() / 1000.0
+    }
+
+    fn result(&self, word: &str, was_corrected: bool, confidence: f64) -> StavaResult {
+        StavaResult {
+            word: word.to_string(),
+            was_corrected,
+            dictionary: self
+                .dictionary_of(word)
+                .map(|dictionary| dictionary.name.clone()),
+            confidence,
+        }
+    }
+
+    fn uncorrected(&self, word: &str) -> StavaResult {
         StavaResult {
             word: word.to_string(),
             was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
         }
     }
 
+    // Candidates from dictionaries with higher priority come first, then the most frequent ones
+    fn rank(&self, word: &str) -> Option<(i32, u32)> {
+        let count = self.known(word)?;
+        let priority = self
+            .dictionary_of(word)
+            .map_or(0, |dictionary| dictionary.priority);
+        Some((priority, count))
+    }
+
+    pub fn check(&self, text: &str) -> Vec<Misspelling> {
+        // The suggestion and its dictionary for each misspelled word
+        let mut corrections: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
+        let mut result = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let lowercase_line = line.to_lowercase();
+            // Report words as written unless lowercasing moved the characters
+            let written_line = if lowercase_line.len() == line.len() {
+                line
+            } else {
+                &lowercase_line
+            };
+
+            for (start, word) in self.tokenizer.tokenize(&lowercase_line) {
+                let end = start + word.len();
+                let word = normalize(word);
+                if (self.is_known_word(&word) && self.replacements.get(&word).is_none())
+                    || !written_line.is_char_boundary(start)
+                    || !written_line.is_char_boundary(end)
+                {
+                    continue;
+                }
+
+                let (suggestion, dictionary) = match corrections.get(&word) {
+                    Some(correction) => correction.clone(),
+                    None => {
+                        let correction = self.correct_valid(&word);
+                        let correction = match correction.was_corrected {
+                            true => (Some(correction.word), correction.dictionary),
+                            false => (None, None),
+                        };
+                        corrections.insert(word, correction.clone());
+                        correction
+                    }
+                };
+
+                result.push(Misspelling {
+                    line: index + 1,
+                    column: written_line[..start].chars().count() + 1,
+                    word: written_line[start..end].to_string(),
+                    suggestion,
+                    dictionary,
+                });
+            }
+        }
+
+        result
+    }
+
+    // If the word is learned, or is the possessive of a learned word, and is not forbidden
+    fn is_known_word(&self, word: &str) -> bool {
+        let is_learned = |word: &str| self.contains(word) && !self.forbidden.contains(word);
+        is_learned(word) || word.strip_suffix("'s").is_some_and(is_learned)
+    }
+
+    // Learned words with the same phonetic code as the word
+    fn sound_alikes(&self, word: &str) -> &[String] {
+        self.phonetic_index
+            .as_ref()
+            .and_then(|index| index.get(&phonetic::metaphone(word)))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // Frequency count of a word that may be suggested as a correction
+    fn known(&self, word: &str) -> Option<u32> {
+        let count = self.count(word)?;
+        if self.ignored.contains(word) || self.forbidden.contains(word) {
+            return None;
+        }
+        Some(count)
+    }
+
     fn get_edits(&self, word: &str) -> HashSet<String> {
         let splits = self.splits(word);
         HashSet::from_iter(
@@ -101,6 +853,7 @@ impl Stava {
                 self.transposes(&splits),
                 self.replaces(&splits),
                 self.inserts(&splits),
+                self.apostrophe_inserts(&splits),
             ]
             .concat(),
         )
@@ -108,8 +861,8 @@ impl Stava {
 
     fn splits<'a>(&self, word: &'a str) -> Vec<(&'a str, &'a str)> {
         let mut result: Vec<(&str, &str)> = Vec::with_capacity(word.len() + 1);
-        let range = 0..result.capacity();
-        for i in range {
+        let indices = word.char_indices().map(|(i, _)| i).chain([word.len()]);
+        for i in indices {
             let left = &word[..i];
             let right = &word[i..];
             result.push((left, right));
@@ -121,22 +874,23 @@ impl Stava {
         let mut result = Vec::with_capacity(words.len() - 1);
         for (left, right) in words {
             if !right.is_empty() {
-                result.push([left, &right[1..]].concat());
+                result.push([left, skip_chars(right, 1)].concat());
             }
         }
         result
     }
 
     fn transposes(&self, words: &[(&str, &str)]) -> Vec<String> {
-        let mut result = Vec::with_capacity(words.len() - 2);
+        let mut result = Vec::with_capacity(words.len().saturating_sub(2));
         for (left, right) in words {
-            if right.len() > 1 {
+            let mut chars = right.chars();
+            if let (Some(first), Some(second)) = (chars.next(), chars.next()) {
                 result.push(
                     [
                         left,
-                        right.chars().nth(1).unwrap().to_string().as_str(),
-                        right.chars().next().unwrap().to_string().as_str(),
-                        &right[2..],
+                        second.to_string().as_str(),
+                        first.to_string().as_str(),
+                        chars.as_str(),
                     ]
                     .concat(),
                 );
@@ -149,34 +903,102 @@ impl Stava {
         let mut result = Vec::new();
         for (left, right) in words {
             if !right.is_empty() {
-                for letter in ENG_ALPHABET.iter() {
-                    result.push([left, letter, &right[1..]].concat());
+                for letter in self.alphabet.iter() {
+                    result.push([left, letter.as_str(), skip_chars(right, 1)].concat());
                 }
             }
         }
         result
     }
 
+    // Apostrophes are inserted within words only, and separately from the other edits so they
+    // are not part of the alphabet
+    fn apostrophe_inserts(&self, words: &[(&str, &str)]) -> Vec<String> {
+        let mut result = Vec::with_capacity(words.len());
+        for (left, right) in words {
+            if !left.is_empty() && !right.is_empty() {
+                result.push([left, "'", right].concat());
+            }
+        }
+        result
+    }
+
     fn inserts(&self, words: &[(&str, &str)]) -> Vec<String> {
-        let mut result = Vec::with_capacity(words.len() * ENG_ALPHABET.len());
+        let mut result = Vec::with_capacity(words.len() * self.alphabet.len());
         for (left, right) in words {
-            for letter in ENG_ALPHABET.iter() {
-                result.push([left, *letter, right].concat());
+            for letter in self.alphabet.iter() {
+                result.push([left, letter.as_str(), right].concat());
             }
         }
         result
     }
 }
 
+// Typographic apostrophes are stored as ASCII apostrophes
+fn normalize(word: &str) -> String {
+    word.replace('\u{2019}', "'")
+}
+
+// The index of the last byte of the longest prefix of `text` that can be learned on its own.
+// ASCII bytes are never part of a multi-byte character so splitting after one keeps both words
+// and characters whole.
+fn chunk_end(text: &[u8]) -> Option<usize> {
+    text.iter().rposition(u8::is_ascii_whitespace).or_else(|| {
+        if text.len() > MAX_PENDING_LEN {
+            text.iter()
+                .rposition(|byte| byte.is_ascii() && !byte.is_ascii_alphanumeric())
+        } else {
+            None
+        }
+    })
+}
+
+// Calls `handle` with the text read from `reader`, in chunks that do not split words so that large
+// files are read in constant memory
+pub(crate) fn read_chunks<R: BufRead>(
+    mut reader: R,
+    mut handle: impl FnMut(&str),
+) -> io::Result<()> {
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.is_empty() {
+            break;
+        }
+        pending.extend_from_slice(buf);
+        let len = buf.len();
+        reader.consume(len);
+
+        if let Some(split) = chunk_end(&pending) {
+            let rest = pending.split_off(split + 1);
+            handle(&String::from_utf8_lossy(&pending));
+            pending = rest;
+        }
+    }
+    handle(&String::from_utf8_lossy(&pending));
+    Ok(())
+}
+
+// The rest of `text` after the first `n` characters
+fn skip_chars(text: &str, n: usize) -> &str {
+    text.char_indices()
+        .nth(n)
+        .map_or("", |(index, _)| &text[index..])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use regex::Regex;
+
     #[test]
     fn test_splits() {
-        let stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let stava = Stava::default();
         let actual = stava.splits("monkey");
         let expected = vec![
             ("", "monkey"),
@@ -192,9 +1014,7 @@ mod tests {
 
     #[test]
     fn test_deletes() {
-        let stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let stava = Stava::default();
         let splits = vec![
             ("", "monkey"),
             ("m", "onkey"),
@@ -204,16 +1024,14 @@ mod tests {
             ("monke", "y"),
             ("monkey", ""),
         ];
-        let actual = stava.deletes(&*splits);
+        let actual = stava.deletes(&splits);
         let expected = vec!["onkey", "mnkey", "mokey", "money", "monky", "monke"];
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_transposes() {
-        let stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let stava = Stava::default();
         let splits = vec![
             ("", "monkey"),
             ("m", "onkey"),
@@ -223,16 +1041,14 @@ mod tests {
             ("monke", "y"),
             ("monkey", ""),
         ];
-        let actual = stava.transposes(&*splits);
+        let actual = stava.transposes(&splits);
         let expected = vec!["omnkey", "mnokey", "mokney", "moneky", "monkye"];
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_replaces() {
-        let stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let stava = Stava::default();
         let splits = vec![
             ("", "monkey"),
             ("m", "onkey"),
@@ -242,7 +1058,7 @@ mod tests {
             ("monke", "y"),
             ("monkey", ""),
         ];
-        let actual = stava.replaces(&*splits);
+        let actual = stava.replaces(&splits);
         let expected = vec![
             "aonkey", "bonkey", "conkey", "donkey", "eonkey", "fonkey", "gonkey", "honkey",
             "ionkey", "jonkey", "konkey", "lonkey", "monkey", "nonkey", "oonkey", "ponkey",
@@ -270,9 +1086,7 @@ mod tests {
 
     #[test]
     fn test_inserts() {
-        let stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let stava = Stava::default();
         let splits = vec![
             ("", "monkey"),
             ("m", "onkey"),
@@ -282,7 +1096,7 @@ mod tests {
             ("monke", "y"),
             ("monkey", ""),
         ];
-        let actual = stava.inserts(&*splits);
+        let actual = stava.inserts(&splits);
         let expected = vec![
             "amonkey", "bmonkey", "cmonkey", "dmonkey", "emonkey", "fmonkey", "gmonkey", "hmonkey",
             "imonkey", "jmonkey", "kmonkey", "lmonkey", "mmonkey", "nmonkey", "omonkey", "pmonkey",
@@ -313,9 +1127,7 @@ mod tests {
 
     #[test]
     fn test_learn_word_freq() {
-        let mut stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let mut stava = Stava::default();
 
         stava.learn("spelling spelling spelling bicycle");
 
@@ -329,9 +1141,7 @@ mod tests {
 
     #[test]
     fn test_learn_text_parse() {
-        let mut stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let mut stava = Stava::default();
 
         stava.learn("(spelling) spelling, spelling. spelling! spelling22 [spelling]-^spelling#");
 
@@ -342,92 +1152,605 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_learn_from_reader() {
+        let mut stava = Stava::builder()
+            .tokenizer(Regex::new(r"[a-zåäö]+").unwrap())
+            .build()
+            .unwrap();
+        let text = "spelling  bicycle\nsmörgåsbord spelling";
+
+        // A small buffer makes words and characters span buffer boundaries
+        for capacity in 1..8 {
+            let reader = io::BufReader::with_capacity(capacity, text.as_bytes());
+            stava.learn_from_reader(reader).unwrap();
+        }
+
+        assert_eq!(stava.count("spelling"), Some(14));
+        assert_eq!(stava.count("bicycle"), Some(7));
+        assert_eq!(stava.count("smörgåsbord"), Some(7));
+        assert_eq!(stava.len(), 3);
+    }
+
     #[test]
     fn test_correct() {
-        let mut stava = Stava {
-            words_w_count: HashMap::new(),
-        };
+        let mut stava = Stava::default();
 
         stava.learn("spelling inconvenient bicycle corrected arranged poetry word");
 
         // insert
         let word = "speling";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "spelling".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.5,
         };
         assert_eq!(actual, expected);
 
         // insert 2
         let word = "inconvient";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "inconvenient".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
         };
         assert_eq!(actual, expected);
 
         // replace
         let word = "bycyle";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "bicycle".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
         };
         assert_eq!(actual, expected);
 
         // replace 2
         let word = "korrectud";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "corrected".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
         };
         assert_eq!(actual, expected);
 
         // delete
         let word = "arrainged";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "arranged".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.5,
         };
         assert_eq!(actual, expected);
 
         // transpose
         let word = "peotry";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "poetry".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.5,
         };
         assert_eq!(actual, expected);
 
         // transpose + delete
         let word = "peotryy";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "poetry".to_string(),
             was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
         };
         assert_eq!(actual, expected);
 
         // known word
         let word = "word";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "word".to_string(),
             was_corrected: false,
+            dictionary: None,
+            confidence: 1.0,
         };
         assert_eq!(actual, expected);
 
         // unknown word
         let word = "quintessential";
-        let actual = stava.correct(word);
+        let actual = stava.correct(word).unwrap();
         let expected = StavaResult {
             word: "quintessential".to_string(),
             was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_correct_contractions() {
+        let mut stava = Stava::default();
+        stava.learn("done done done don't they\u{2019}re the word");
+
+        // missing apostrophe is preferred over more frequent words
+        let actual = stava.correct("dont").unwrap();
+        assert_eq!(actual.word, "don't");
+
+        // typographic apostrophes are normalized
+        let actual = stava.correct("they\u{2019}re").unwrap();
+        let expected = StavaResult {
+            word: "they're".to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 1.0,
+        };
+        assert_eq!(actual, expected);
+
+        let actual = stava.correct("theyre").unwrap();
+        assert_eq!(actual.word, "they're");
+
+        // possessives of known words are known
+        let actual = stava.correct("word's").unwrap();
+        assert!(!actual.was_corrected);
+    }
+
+    #[test]
+    fn test_correct_sound_alikes() {
+        let mut stava = Stava::default();
+        stava.learn("knowledge of physics and the phone");
+
+        let actual = stava.correct("fone").unwrap();
+        let expected = StavaResult {
+            word: "phone".to_string(),
+            was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
+        };
+        assert_eq!(actual, expected);
+
+        // sound alikes are used when no edits are close enough
+        let actual = stava.correct("nollij").unwrap();
+        assert_eq!(actual.word, "knowledge");
+        stava.max_distance = 1;
+        assert_eq!(stava.correct("fone").unwrap().word, "phone");
+
+        // but not when their length is too different
+        stava.learn("xylophone");
+        assert!(!stava.correct("slfn").unwrap().was_corrected);
+    }
+
+    #[test]
+    fn test_correct_replacements() {
+        let mut stava = Stava::default();
+        stava.learn("the teh end kubernetes");
+        stava.replacements.insert("teh", "the");
+        stava.replacements.insert("kuberentes", "Kubernetes");
+
+        let actual = stava.correct("teh").unwrap();
+        let expected = StavaResult {
+            word: "the".to_string(),
+            was_corrected: true,
+            dictionary: None,
+            confidence: 1.0,
+        };
+        assert_eq!(actual, expected);
+
+        let actual = stava.correct("kuberentes").unwrap();
+        assert_eq!(actual.word, "Kubernetes");
+
+        let actual = stava.check("Teh end");
+        let expected = vec![Misspelling {
+            line: 1,
+            column: 1,
+            word: "Teh".to_string(),
+            suggestion: Some("the".to_string()),
+            dictionary: None,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_correct_forbidden_words() {
+        let mut stava = Stava::default();
+        stava.learn("a lot alot allot stavacloud stava cloud");
+        stava.forbidden.insert("alot", Some("a lot"));
+        stava.forbidden.insert("stavacloud", None);
+
+        // forbidden words are corrected to the preferred word
+        let actual = stava.correct("alot").unwrap();
+        let expected = StavaResult {
+            word: "a lot".to_string(),
+            was_corrected: true,
+            dictionary: None,
+            confidence: 1.0,
+        };
+        assert_eq!(actual, expected);
+
+        // forbidden words are never suggested
+        assert_eq!(stava.correct("alott").unwrap().word, "allot");
+
+        let actual = stava.check("stavacloud is known");
+        assert_eq!(actual[0].word, "stavacloud");
+        assert_ne!(actual[0].suggestion.as_deref(), Some("stavacloud"));
+    }
+
+    #[test]
+    fn test_correct_short_words() {
+        let mut stava = Stava::default();
+        stava.learn("poetry");
+
+        let actual = stava.correct("q").unwrap();
+        let expected = StavaResult {
+            word: "q".to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_correct_invalid_word() {
+        let mut stava = Stava::default();
+        stava.learn("hello");
+
+        let actual = stava.correct("héllo");
+        assert!(matches!(actual, Err(StavaError::InvalidWord(word)) if word == "héllo"));
+
+        let actual = stava.correct("");
+        assert!(matches!(actual, Err(StavaError::InvalidWord(_))));
+
+        for word in ["hello world", "a+b", "3000"] {
+            assert!(matches!(
+                stava.correct(word),
+                Err(StavaError::InvalidWord(_))
+            ));
+        }
+        assert!(stava.correct("don't").is_ok());
+    }
+
+    #[test]
+    fn test_correct_confidence() {
+        let mut stava = Stava::default();
+        stava.learn(&"spelling ".repeat(9));
+        stava.learn("spewing");
+
+        // Common with a rare runner-up
+        assert_eq!(stava.correct("speling").unwrap().confidence, 0.506);
+
+        // As common as the runner-up
+        stava.learn(&"spewing ".repeat(8));
+        let actual = stava.correct("speling").unwrap();
+        assert!(actual.was_corrected);
+        assert!(actual.confidence < 0.506);
+
+        // More edits
+        assert!(stava.correct("spelingg").unwrap().confidence < actual.confidence);
+    }
+
+    #[test]
+    fn test_correct_min_confidence() {
+        let mut stava = Stava::builder().min_confidence(0.4).build().unwrap();
+        stava.learn("the the the the rust");
+
+        assert_eq!(stava.correct("thw").unwrap().word, "the");
+        let expected = StavaResult {
+            word: "rustc".to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
+        };
+        assert_eq!(stava.correct("rustc").unwrap(), expected);
+        assert_eq!(stava.check("rustc")[0].suggestion, None);
+    }
+
+    #[test]
+    fn test_correct_min_confidence_with_contraction() {
+        let mut stava = Stava::builder().min_confidence(0.4).build().unwrap();
+        stava.learn(&format!("{} don't", "done ".repeat(20)));
+
+        // the rare contraction is not confident, so the edits are ranked
+        assert_eq!(stava.correct("dont").unwrap().word, "done");
+
+        stava.learn("isn't");
+        let actual = stava.correct("isnt").unwrap();
+        assert_eq!(actual.word, "isnt");
+        assert!(!actual.was_corrected);
+    }
+
+    #[test]
+    fn test_correct_max_distance() {
+        let mut stava = Stava::default();
+        stava.learn("poetry");
+        // words that sound alike are candidates whatever the maximum distance
+        stava.set_phonetic_matching(false);
+
+        stava.max_distance = 1;
+        let actual = stava.correct("peotryy").unwrap();
+        let expected = StavaResult {
+            word: "peotryy".to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
+        };
+        assert_eq!(actual, expected);
+
+        stava.max_distance = 2;
+        let actual = stava.correct("peotryy").unwrap();
+        let expected = StavaResult {
+            word: "poetry".to_string(),
+            was_corrected: true,
+            dictionary: None,
+            confidence: 0.25,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_stava_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Stava>();
+    }
+
+    #[test]
+    fn test_correct_from_threads() {
+        let mut stava = Stava::default();
+        stava.learn("spelling bicycle");
+
+        std::thread::scope(|scope| {
+            let handles = [
+                scope.spawn(|| stava.correct("speling").unwrap()),
+                scope.spawn(|| stava.correct("bycycle").unwrap()),
+            ];
+            let actual: Vec<String> = handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap().word)
+                .collect();
+            assert_eq!(actual, vec!["spelling", "bicycle"]);
+        });
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_correct_all() {
+        let mut stava = Stava::default();
+        stava.learn("spelling bicycle poetry");
+
+        let actual = stava.correct_all(&["speling", "poetry", "héllo", "bycycle"]);
+
+        assert_eq!(actual.len(), 4);
+        assert_eq!(actual[0].as_ref().unwrap().word, "spelling");
+        assert_eq!(
+            actual[1].as_ref().unwrap(),
+            &StavaResult {
+                word: "poetry".to_string(),
+                was_corrected: false,
+                dictionary: None,
+                confidence: 1.0,
+            }
+        );
+        assert!(matches!(actual[2], Err(StavaError::InvalidWord(_))));
+        assert_eq!(actual[3].as_ref().unwrap().word, "bicycle");
+    }
+
+    #[test]
+    fn test_check() {
+        let mut stava = Stava::default();
+        stava.learn("the spelling of a word and");
+
+        let actual = stava.check("The speling of a\nwurd, and a qwxz.");
+        let expected = vec![
+            Misspelling {
+                line: 1,
+                column: 5,
+                word: "speling".to_string(),
+                suggestion: Some("spelling".to_string()),
+                dictionary: None,
+            },
+            Misspelling {
+                line: 2,
+                column: 1,
+                word: "wurd".to_string(),
+                suggestion: Some("word".to_string()),
+                dictionary: None,
+            },
+            Misspelling {
+                line: 2,
+                column: 13,
+                word: "qwxz".to_string(),
+                suggestion: None,
+                dictionary: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_check_reports_dictionary() {
+        let mut stava = Stava::default();
+        stava.learn("the word");
+        stava.learn_as(&Dictionary::new("glossary"), "spelling");
+
+        let actual = stava.check("speling wurd");
+
+        assert_eq!(actual[0].dictionary, Some("glossary".to_string()));
+        assert_eq!(actual[1].dictionary, None);
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("en".parse(), Ok(Language::English));
+        assert_eq!("English".parse(), Ok(Language::English));
+        assert_eq!(
+            "sv".parse::<Language>(),
+            Err("Unsupported language [sv]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_learn_as_dictionary() {
+        let mut stava = Stava::default();
+        stava.learn("spelling bicycle");
+        stava.learn_as(&Dictionary::new("glossary").with_weight(10), "spelling");
+
+        assert_eq!(stava.count("spelling"), Some(11));
+        assert_eq!(stava.count("bicycle"), Some(1));
+        assert_eq!(stava.dictionary_of("spelling").unwrap().name, "glossary");
+        assert_eq!(stava.dictionary_of("bicycle"), None);
+        assert_eq!(stava.dictionaries().len(), 1);
+
+        let actual = stava.correct("speling").unwrap();
+        let expected = StavaResult {
+            word: "spelling".to_string(),
+            was_corrected: true,
+            dictionary: Some("glossary".to_string()),
+            confidence: 0.561,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_correct_prefers_dictionary_with_higher_priority() {
+        let mut stava = Stava::default();
+        stava.learn_as(&Dictionary::new("words"), "kitten kitten kitten");
+        stava.learn_as(&Dictionary::new("glossary").with_priority(1), "kitted");
+
+        // Fewer occurrences, but from a dictionary with higher priority
+        let actual = stava.correct("kittex").unwrap();
+        assert_eq!(actual.word, "kitted");
+        assert_eq!(actual.dictionary, Some("glossary".to_string()));
+
+        // The dictionary with the highest priority is reported for words in several dictionaries
+        stava.learn_as(&Dictionary::new("words"), "kitted");
+        stava.learn_as(&Dictionary::new("names").with_priority(2), "kitten");
+        assert_eq!(stava.dictionary_of("kitted").unwrap().name, "glossary");
+        assert_eq!(stava.dictionary_of("kitten").unwrap().name, "names");
+    }
+
+    #[test]
+    fn test_correct_combined_ranking() {
+        let text = format!("spewing {}", "sapling ".repeat(1000));
+        let build = |ranking: Ranking| {
+            let mut stava = Stava::builder().ranking(ranking).build().unwrap();
+            stava.learn(&text);
+            stava
+        };
+
+        // A rare word with one edit wins over a common word with two
+        let stava = build(Ranking::Norvig);
+        assert_eq!(stava.correct("speling").unwrap().word, "spewing");
+
+        // Unless the common word is frequent enough to make up for the extra edit
+        let stava = build(Ranking::combined());
+        assert_eq!(stava.correct("speling").unwrap().word, "sapling");
+        assert_eq!(stava.explain("speling").unwrap().rule, Rule::HighestScore);
+
+        let stava = build(Ranking::Combined {
+            edit_penalty: 10_000.0,
+        });
+        assert_eq!(stava.correct("speling").unwrap().word, "spewing");
+
+        // Known words are still not corrected
+        assert!(!stava.correct("sapling").unwrap().was_corrected);
+    }
+
+    #[test]
+    fn test_ranking_from_str() {
+        assert_eq!("norvig".parse(), Ok(Ranking::Norvig));
+        assert_eq!(
+            "combined".parse(),
+            Ok(Ranking::Combined {
+                edit_penalty: DEFAULT_EDIT_PENALTY
+            })
+        );
+        assert!("fastest".parse::<Ranking>().is_err());
+    }
+
+    #[test]
+    fn test_learn_frequencies() {
+        let mut list = FrequencyList::default();
+        list.insert("Spelling", 100);
+        list.insert("spewing", 3);
+
+        let mut stava = Stava::default();
+        stava.learn("spewing");
+        stava.learn_frequencies(&list);
+
+        assert_eq!(stava.count("spelling"), Some(100));
+        assert_eq!(stava.count("spewing"), Some(4));
+        assert_eq!(stava.correct("speling").unwrap().word, "spelling");
+
+        stava.learn_frequencies_as(&Dictionary::new("ngrams").with_weight(2), &list);
+        assert_eq!(stava.count("spelling"), Some(300));
+        assert_eq!(stava.dictionary_of("spelling").unwrap().name, "ngrams");
+    }
+
+    #[test]
+    fn test_learn_hunspell() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hunspell");
+        let hunspell = Hunspell::load(&dir.join("en.dic")).unwrap();
+
+        let mut stava = Stava::default();
+        stava.learn("walk walk walk");
+        stava.learn_hunspell_as(&Dictionary::new("en"), hunspell);
+
+        // Inflected words are known without being learned
+        assert_eq!(stava.count("walked"), Some(4));
+        assert!(stava.contains("unhappiness"));
+        assert!(!stava.contains("walkd"));
+        assert_eq!(stava.len(), 5);
+        assert_eq!(stava.dictionary_of("cried").unwrap().name, "en");
+
+        let actual = stava.correct("walkinf").unwrap();
+        let expected = StavaResult {
+            word: "walking".to_string(),
+            was_corrected: true,
+            dictionary: Some("en".to_string()),
+            confidence: 0.627,
+        };
+        assert_eq!(actual, expected);
+        assert_eq!(stava.correct("unhapiness").unwrap().word, "unhappiness");
+        assert!(!stava.correct("cried").unwrap().was_corrected);
+    }
+
+    #[test]
+    fn test_merge_personal() {
+        let mut stava = Stava::default();
+        stava.learn("spelling spelling bicycle");
+
+        let mut dict = PersonalDictionary::default();
+        dict.add("rustacean");
+        dict.ignore("spelling");
+        stava.merge_personal(&dict);
+
+        // words in the personal dictionary are accepted
+        let actual = stava.correct("rustacean").unwrap();
+        let expected = StavaResult {
+            word: "rustacean".to_string(),
+            was_corrected: false,
+            dictionary: Some(PERSONAL_DICTIONARY_NAME.to_string()),
+            confidence: 1.0,
+        };
+        assert_eq!(actual, expected);
+
+        // words in the ignore list are not suggested
+        let actual = stava.correct("speling").unwrap();
+        let expected = StavaResult {
+            word: "speling".to_string(),
+            was_corrected: false,
+            dictionary: None,
+            confidence: 0.0,
         };
         assert_eq!(actual, expected);
     }
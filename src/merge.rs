@@ -0,0 +1,129 @@
+use std::collections::BTreeSet;
+
+use crate::Stava;
+
+/// How the counts of a word are combined when two [`Stava`] are merged
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MergeStrategy {
+    // The counts are added
+    #[default]
+    Sum,
+    // The larger count is kept
+    Max,
+    // The average of the counts, where the count of the merged words has this weight between 0
+    // and 1 and the count of the words merged into has the rest. Counts are rounded, but never
+    // below 1 so that every word is still learned.
+    Weighted(f64),
+}
+
+impl MergeStrategy {
+    /// The count of a word counted `count` times in the words merged into and `other` times in
+    /// the merged words. A count of 0 means that the word was not learned.
+    pub fn combine(&self, count: u32, other: u32) -> u32 {
+        match self {
+            MergeStrategy::Sum => count.saturating_add(other),
+            MergeStrategy::Max => count.max(other),
+            MergeStrategy::Weighted(weight) => {
+                let weight = weight.clamp(0.0, 1.0);
+                let average = f64::from(count) * (1.0 - weight) + f64::from(other) * weight;
+                average.round().clamp(1.0, f64::from(u32::MAX)) as u32
+            }
+        }
+    }
+}
+
+impl Stava {
+    /// Learn the words of `other`, combining the counts of the words learned by both with
+    /// `strategy`.
+    ///
+    /// The dictionaries of `other` are added, and a word learned by both is reported as coming
+    /// from the dictionary with the highest priority. The ignored, replaced and forbidden words of
+    /// `other` are not merged.
+    pub fn merge(&mut self, other: &Stava, strategy: MergeStrategy) {
+        let dictionaries: Vec<usize> = other
+            .dictionaries
+            .iter()
+            .map(|dictionary| self.dictionary_index(dictionary))
+            .collect();
+
+        let words: BTreeSet<String> = match strategy {
+            // Only the weighted average changes the counts of the words that are not in `other`
+            MergeStrategy::Weighted(_) => self
+                .words_w_count
+                .keys()
+                .chain(other.words_w_count.keys())
+                .cloned()
+                .collect(),
+            MergeStrategy::Sum | MergeStrategy::Max => {
+                other.words_w_count.keys().cloned().collect()
+            }
+        };
+        for word in words {
+            if let Some(&origin) = other.origins.get(&word) {
+                self.set_origin(&word, dictionaries[origin]);
+            }
+            let count = strategy.combine(self.count_of(&word), other.count_of(&word));
+            self.set_count(word, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dictionary::Dictionary;
+
+    fn merge(strategy: MergeStrategy) -> Stava {
+        let mut stava = Stava::default();
+        stava.learn("the the the cat");
+        let mut other = Stava::default();
+        other.learn("the dog");
+
+        stava.merge(&other, strategy);
+        stava
+    }
+
+    #[test]
+    fn test_merge_sum() {
+        let stava = merge(MergeStrategy::Sum);
+
+        assert_eq!(stava.count("the"), Some(4));
+        assert_eq!(stava.count("cat"), Some(1));
+        assert_eq!(stava.count("dog"), Some(1));
+        assert_eq!(stava.total_count, 6);
+        assert_eq!(stava.correct("dgo").unwrap().word, "dog");
+    }
+
+    #[test]
+    fn test_merge_max() {
+        let stava = merge(MergeStrategy::Max);
+
+        assert_eq!(stava.count("the"), Some(3));
+        assert_eq!(stava.count("dog"), Some(1));
+        assert_eq!(stava.total_count, 5);
+    }
+
+    #[test]
+    fn test_merge_weighted() {
+        let stava = merge(MergeStrategy::Weighted(0.5));
+
+        assert_eq!(stava.count("the"), Some(2));
+        assert_eq!(stava.count("cat"), Some(1));
+        assert_eq!(stava.count("dog"), Some(1));
+        assert_eq!(stava.total_count, 4);
+    }
+
+    #[test]
+    fn test_merge_keeps_dictionaries() {
+        let mut stava = Stava::default();
+        stava.learn_as(&Dictionary::new("docs"), "cat");
+        let mut other = Stava::default();
+        other.learn_as(&Dictionary::new("terms").with_priority(1), "cat dog");
+
+        stava.merge(&other, MergeStrategy::Sum);
+
+        assert_eq!(stava.dictionary_of("cat").unwrap().name, "terms");
+        assert_eq!(stava.dictionary_of("dog").unwrap().name, "terms");
+    }
+}
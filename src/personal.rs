@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, StavaError};
+
+const WORDS_FILE_NAME: &str = "dictionary.txt";
+const IGNORED_FILE_NAME: &str = "ignore.txt";
+const BAD_WORDS_FILE_NAME: &str = "bad.txt";
+
+/// A dictionary of words kept between runs.
+///
+/// Words in the dictionary are always accepted, words in the ignore list are never suggested and
+/// bad words are never accepted or suggested. The lists are stored as plain text files with one
+/// word per line in a single directory.
+#[derive(Debug, Default, PartialEq)]
+pub struct PersonalDictionary {
+    words: BTreeSet<String>,
+    ignored: BTreeSet<String>,
+    bad_words: BTreeSet<String>,
+}
+
+impl PersonalDictionary {
+    /// The directory used when no other is given: `$XDG_DATA_HOME/stava`, falling back to
+    /// `$HOME/.local/share/stava`
+    pub fn default_dir() -> Option<PathBuf> {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .filter(|home| !home.is_empty())
+                    .map(|home| Path::new(&home).join(".local/share"))
+            })
+            .map(|dir| dir.join("stava"))
+    }
+
+    /// Read the dictionary from `dir`. Missing files are treated as empty lists.
+    pub fn load(dir: &Path) -> Result<PersonalDictionary> {
+        Ok(PersonalDictionary {
+            words: read_word_list(&dir.join(WORDS_FILE_NAME))?,
+            ignored: read_word_list(&dir.join(IGNORED_FILE_NAME))?,
+            bad_words: read_word_list(&dir.join(BAD_WORDS_FILE_NAME))?,
+        })
+    }
+
+    /// Write the dictionary to `dir`, creating the directory if needed
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir).map_err(|err| StavaError::io(dir, err))?;
+        write_word_list(&dir.join(WORDS_FILE_NAME), &self.words)?;
+        write_word_list(&dir.join(IGNORED_FILE_NAME), &self.ignored)?;
+        write_word_list(&dir.join(BAD_WORDS_FILE_NAME), &self.bad_words)
+    }
+
+    /// Add a word to the dictionary. Returns false if it was already there
+    pub fn add(&mut self, word: &str) -> bool {
+        self.words.insert(word.to_lowercase())
+    }
+
+    /// Remove a word from the dictionary. Returns false if it was not there
+    pub fn remove(&mut self, word: &str) -> bool {
+        self.words.remove(&word.to_lowercase())
+    }
+
+    /// Add a word to the ignore list. Returns false if it was already there
+    pub fn ignore(&mut self, word: &str) -> bool {
+        self.ignored.insert(word.to_lowercase())
+    }
+
+    /// Remove a word from the ignore list. Returns false if it was not there
+    pub fn unignore(&mut self, word: &str) -> bool {
+        self.ignored.remove(&word.to_lowercase())
+    }
+
+    /// Add a word to the bad words. Returns false if it was already there
+    pub fn add_bad_word(&mut self, word: &str) -> bool {
+        self.bad_words.insert(word.to_lowercase())
+    }
+
+    /// Remove a word from the bad words. Returns false if it was not there
+    pub fn remove_bad_word(&mut self, word: &str) -> bool {
+        self.bad_words.remove(&word.to_lowercase())
+    }
+
+    /// The words in the dictionary, in alphabetical order
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    /// The words in the ignore list, in alphabetical order
+    pub fn ignored(&self) -> impl Iterator<Item = &str> {
+        self.ignored.iter().map(String::as_str)
+    }
+
+    /// The bad words, in alphabetical order
+    pub fn bad_words(&self) -> impl Iterator<Item = &str> {
+        self.bad_words.iter().map(String::as_str)
+    }
+}
+
+fn read_word_list(path: &Path) -> Result<BTreeSet<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_lowercase)
+            .collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+        Err(err) => Err(StavaError::io(path, err)),
+    }
+}
+
+fn write_word_list(path: &Path, words: &BTreeSet<String>) -> Result<()> {
+    let mut contents = String::new();
+    for word in words {
+        contents.push_str(word);
+        contents.push('\n');
+    }
+    fs::write(path, contents).map_err(|err| StavaError::io(path, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut dict = PersonalDictionary::default();
+        assert!(dict.add("Rustacean"));
+        assert!(!dict.add("rustacean"));
+        assert!(dict.add("clippy"));
+        assert!(dict.ignore("teh"));
+        assert!(dict.add_bad_word("alot"));
+        dict.save(dir.path()).unwrap();
+
+        let actual = PersonalDictionary::load(dir.path()).unwrap();
+        assert_eq!(actual, dict);
+        assert_eq!(
+            actual.words().collect::<Vec<_>>(),
+            vec!["clippy", "rustacean"]
+        );
+        assert_eq!(actual.ignored().collect::<Vec<_>>(), vec!["teh"]);
+        assert_eq!(actual.bad_words().collect::<Vec<_>>(), vec!["alot"]);
+    }
+
+    #[test]
+    fn test_load_missing_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let actual = PersonalDictionary::load(&dir.path().join("missing")).unwrap();
+
+        assert_eq!(actual, PersonalDictionary::default());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut dict = PersonalDictionary::default();
+        dict.add("clippy");
+        dict.ignore("teh");
+        dict.add_bad_word("alot");
+
+        assert!(dict.remove("Clippy"));
+        assert!(!dict.remove("clippy"));
+        assert!(dict.unignore("teh"));
+        assert!(!dict.unignore("teh"));
+        assert!(dict.remove_bad_word("alot"));
+        assert!(!dict.remove_bad_word("alot"));
+        assert_eq!(dict, PersonalDictionary::default());
+    }
+}
@@ -0,0 +1,146 @@
+/// The Metaphone code of an English word, words that sound alike have the same code.
+///
+/// Only the letters a-z are used, anything else in the word is skipped.
+pub fn metaphone(word: &str) -> String {
+    let mut letters: Vec<u8> = word
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase())
+        .collect();
+
+    letters.dedup_by(|a, b| a == b && *a != b'C');
+    if [b"KN", b"GN", b"PN", b"AE", b"WR"]
+        .iter()
+        .any(|prefix| letters.starts_with(*prefix))
+    {
+        letters.remove(0);
+    }
+    if letters.ends_with(b"MB") {
+        letters.pop();
+    }
+
+    let at = |index: usize| letters.get(index).copied().unwrap_or(0);
+    let mut code = String::with_capacity(letters.len());
+    for (i, &letter) in letters.iter().enumerate() {
+        let prev = if i > 0 { letters[i - 1] } else { 0 };
+        let next = at(i + 1);
+        let after_next = at(i + 2);
+
+        match letter {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                if i == 0 {
+                    code.push(letter as char);
+                }
+            }
+            b'C' => {
+                if next == b'I' && after_next == b'A' {
+                    code.push('X');
+                } else if next == b'H' {
+                    code.push(if prev == b'S' { 'K' } else { 'X' });
+                } else if matches!(next, b'I' | b'E' | b'Y') {
+                    code.push('S');
+                } else {
+                    code.push('K');
+                }
+            }
+            b'D' => {
+                if next == b'G' && matches!(after_next, b'E' | b'I' | b'Y') {
+                    code.push('J');
+                } else {
+                    code.push('T');
+                }
+            }
+            b'G' => {
+                let is_silent = (next == b'H' && !(i + 2 == letters.len() || is_vowel(after_next)))
+                    || (next == b'N' && (i + 2 == letters.len() || &letters[i + 1..] == b"NED"))
+                    || (prev == b'D' && matches!(next, b'E' | b'I' | b'Y'));
+                if is_silent {
+                    continue;
+                }
+                if matches!(next, b'E' | b'I' | b'Y') && prev != b'G' {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            }
+            b'H' => {
+                let is_silent = (is_vowel(prev) && !is_vowel(next))
+                    || matches!(prev, b'C' | b'S' | b'P' | b'T' | b'G')
+                    || (i == 1 && prev == b'W');
+                if !is_silent {
+                    code.push('H');
+                }
+            }
+            b'K' => {
+                if prev != b'C' {
+                    code.push('K');
+                }
+            }
+            b'P' => code.push(if next == b'H' { 'F' } else { 'P' }),
+            b'Q' => code.push('K'),
+            b'S' => {
+                if next == b'H' || (next == b'I' && matches!(after_next, b'O' | b'A')) {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            b'T' => {
+                if next == b'I' && matches!(after_next, b'O' | b'A') {
+                    code.push('X');
+                } else if next == b'H' {
+                    code.push('0');
+                } else if !(next == b'C' && after_next == b'H') {
+                    code.push('T');
+                }
+            }
+            b'V' => code.push('F'),
+            b'W' => {
+                if (i == 0 && next == b'H') || is_vowel(next) {
+                    code.push('W');
+                }
+            }
+            b'X' => code.push_str(if i == 0 { "S" } else { "KS" }),
+            b'Y' => {
+                if is_vowel(next) {
+                    code.push('Y');
+                }
+            }
+            b'Z' => code.push('S'),
+            _ => code.push(letter as char),
+        }
+    }
+    code
+}
+
+fn is_vowel(letter: u8) -> bool {
+    matches!(letter, b'A' | b'E' | b'I' | b'O' | b'U')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metaphone() {
+        let cases = [
+            ("knowledge", "NLJ"),
+            ("nollij", "NLJ"),
+            ("phone", "FN"),
+            ("fone", "FN"),
+            ("physics", "FSKS"),
+            ("fizzix", "FSKS"),
+            ("thumb", "0M"),
+            ("knight", "NT"),
+            ("school", "SKL"),
+            ("nation", "NXN"),
+            ("whale", "WL"),
+            ("xylophone", "SLFN"),
+            ("don't", "TNT"),
+            ("", ""),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(metaphone(word), expected, "metaphone({:?})", word);
+        }
+    }
+}
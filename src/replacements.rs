@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Result, StavaError};
+use crate::normalize;
+
+const SEPARATOR: &str = "->";
+
+/// A table of words that are always corrected to a given replacement.
+///
+/// The table is read from text files with one `wrong -> right` pair per line. Empty lines and
+/// lines starting with `#` are skipped.
+#[derive(Debug, Default, PartialEq)]
+pub struct Replacements {
+    replacements: BTreeMap<String, String>,
+}
+
+impl Replacements {
+    /// Read the table from `path`
+    pub fn load(path: &Path) -> Result<Replacements> {
+        let mut result = Replacements::default();
+        for (line, wrong, right) in read_lines(path)? {
+            match right {
+                Some(right) => result.insert(&wrong, &right),
+                None => {
+                    return Err(StavaError::InvalidFile {
+                        path: path.to_path_buf(),
+                        line,
+                        message: "Expected a line like `wrong -> right`".to_string(),
+                    })
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Always correct `wrong` to `right`. A previous replacement of `wrong` is overwritten.
+    pub fn insert(&mut self, wrong: &str, right: &str) {
+        self.replacements
+            .insert(normalize(&wrong.to_lowercase()), right.to_string());
+    }
+
+    /// Add all replacements in `other`, overwriting the replacements of the same words
+    pub fn extend(&mut self, other: Replacements) {
+        self.replacements.extend(other.replacements);
+    }
+
+    /// The replacement of a word
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.replacements
+            .get(&normalize(&word.to_lowercase()))
+            .map(String::as_str)
+    }
+
+    /// The wrong words with their replacements, in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.replacements
+            .iter()
+            .map(|(wrong, right)| (wrong.as_str(), right.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty()
+    }
+}
+
+// The line number, word and optional replacement of each `word` or `word -> replacement` line
+pub(crate) fn read_lines(path: &Path) -> Result<Vec<(usize, String, Option<String>)>> {
+    let contents = fs::read_to_string(path).map_err(|err| StavaError::io(path, err))?;
+    let mut result = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (word, replacement) = match line.split_once(SEPARATOR) {
+            Some((word, replacement)) => (word.trim(), Some(replacement.trim())),
+            None => (line, None),
+        };
+        if word.is_empty() || replacement.is_some_and(str::is_empty) {
+            return Err(StavaError::InvalidFile {
+                path: path.to_path_buf(),
+                line: index + 1,
+                message: "Both the wrong and the right word are needed".to_string(),
+            });
+        }
+        result.push((index + 1, word.to_string(), replacement.map(str::to_string)));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replacements.txt");
+        fs::write(
+            &path,
+            "# Common typos\nkuberentes -> Kubernetes\n\n  Teh->the  \nalot -> a lot\n",
+        )
+        .unwrap();
+
+        let actual = Replacements::load(&path).unwrap();
+
+        assert_eq!(
+            actual.iter().collect::<Vec<_>>(),
+            vec![
+                ("alot", "a lot"),
+                ("kuberentes", "Kubernetes"),
+                ("teh", "the")
+            ]
+        );
+        assert_eq!(actual.get("TEH"), Some("the"));
+        assert_eq!(actual.get("the"), None);
+    }
+
+    #[test]
+    fn test_load_invalid_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replacements.txt");
+        fs::write(&path, "teh -> the\nkuberentes Kubernetes\n").unwrap();
+
+        let actual = Replacements::load(&path).unwrap_err();
+
+        assert!(matches!(actual, StavaError::InvalidFile { line: 2, .. }));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{normalize, Stava};
+
+// Longer learned words are not considered when segmenting
+const MAX_WORD_LEN: usize = 24;
+
+impl Stava {
+    /// Split the words in `text` into the most likely sequence of learned words.
+    ///
+    /// Words that are run together are split ("thisis" becomes "this is") and words that are
+    /// split are joined ("spel ling" becomes "spelling"). Parts that are not learned are kept as
+    /// they are.
+    pub fn segment(&self, text: &str) -> Vec<String> {
+        let mut joined = String::new();
+        // The index of the first letter of the word that each letter of `joined` was written in
+        let mut word_starts = Vec::new();
+        for (_, word) in self.tokenizer.tokenize(&text.to_lowercase()) {
+            let start = word_starts.len();
+            for letter in normalize(word).chars() {
+                joined.push(letter);
+                word_starts.push(start);
+            }
+        }
+        let boundaries: Vec<usize> = joined
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([joined.len()])
+            .collect();
+
+        // The log probability of the most likely segmentation of the text up to each boundary,
+        // and the boundary the last word of that segmentation starts at
+        let mut best: Vec<(f64, usize)> = vec![(0.0, 0)];
+        for end in 1..boundaries.len() {
+            // Unknown words are kept whole, however long they are
+            let first_start = end.saturating_sub(MAX_WORD_LEN).min(word_starts[end - 1]);
+            let candidate = (first_start..end)
+                .filter_map(|start| {
+                    let word = &joined[boundaries[start]..boundaries[end]];
+                    let known = match end - start <= MAX_WORD_LEN {
+                        true => self.known(word),
+                        false => None,
+                    };
+                    match known {
+                        Some(count) => Some((best[start].0 + self.log_probability(count), start)),
+                        // Only learned words join the words that were written apart
+                        None if start >= word_starts[end - 1] => {
+                            Some((best[start].0 + self.unknown_log_probability(word), start))
+                        }
+                        None => None,
+                    }
+                })
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+                .unwrap();
+            best.push(candidate);
+        }
+
+        let mut result = Vec::new();
+        let mut end = boundaries.len() - 1;
+        while end > 0 {
+            let start = best[end].1;
+            result.push(joined[boundaries[start]..boundaries[end]].to_string());
+            end = start;
+        }
+        result.reverse();
+        result
+    }
+
+    fn log_probability(&self, count: u32) -> f64 {
+        (f64::from(count) / self.total_count.max(1) as f64).ln()
+    }
+
+    // Words that are not learned get a probability that decreases with their length. It is also
+    // lower for each word, whatever the number of learned words, so splitting them never makes
+    // them more likely.
+    fn unknown_log_probability(&self, word: &str) -> f64 {
+        -(self.total_count as f64 + 1.0).ln() - word.chars().count() as f64 * 10f64.ln()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stava() -> Stava {
+        let mut stava = Stava::default();
+        stava.learn("this is a test of the spell checker and the spelling of words");
+        stava
+    }
+
+    #[test]
+    fn test_segment_splits_words() {
+        let stava = stava();
+
+        assert_eq!(stava.segment("thisis"), vec!["this", "is"]);
+        assert_eq!(stava.segment("spellchecker"), vec!["spell", "checker"]);
+        assert_eq!(
+            stava.segment("This isatest."),
+            vec!["this", "is", "a", "test"]
+        );
+    }
+
+    #[test]
+    fn test_segment_joins_words() {
+        let stava = stava();
+
+        assert_eq!(stava.segment("spel ling"), vec!["spelling"]);
+        assert_eq!(
+            stava.segment("the spel ling of wo rds"),
+            vec!["the", "spelling", "of", "words"]
+        );
+    }
+
+    #[test]
+    fn test_segment_keeps_unknown_words() {
+        let stava = stava();
+
+        assert_eq!(stava.segment("thequokka"), vec!["the", "quokka"]);
+        assert_eq!(stava.segment(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_segment_keeps_unknown_words_with_small_corpus() {
+        let mut stava = Stava::default();
+        stava.learn("the");
+
+        assert_eq!(
+            stava.segment("in to the mat"),
+            vec!["in", "to", "the", "mat"]
+        );
+        assert_eq!(stava.segment("qwx zzy"), vec!["qwx", "zzy"]);
+        // longer than the learned words that are considered
+        assert_eq!(
+            stava.segment("the pneumonoultramicroscopicsilicovolcanoconiosis"),
+            vec!["the", "pneumonoultramicroscopicsilicovolcanoconiosis"]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use serde::Serialize;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{normalize, Stava};
+
+/// What a [`Stava`] has learned, to help choose the files to learn from
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Stats {
+    // The number of learned words, counting every occurrence
+    pub total_count: u64,
+    // The number of different learned words
+    pub unique_words: usize,
+    // The most frequent words, the most frequent first
+    pub top_words: Vec<WordCount>,
+    // The number of different words of each length in characters
+    pub length_distribution: BTreeMap<usize, usize>,
+    // The share of the different words that were learned only once
+    pub singleton_ratio: f64,
+    // The letters of the alphabet that are not in any learned word
+    pub missing_letters: Vec<String>,
+    // The characters in learned words that are not in the alphabet
+    pub other_characters: Vec<char>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct WordCount {
+    pub word: String,
+    pub count: u32,
+}
+
+/// What a [`Stava`] knows about a word
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Lookup {
+    pub word: String,
+    // How many times the word was learned, if it was
+    pub count: Option<u32>,
+    // The position of the word when the words are sorted by count, starting at 1. Words with the
+    // same count have the same rank.
+    pub rank: Option<usize>,
+    // The name of the dictionary the word was learned from, if it was learned from a named one
+    pub dictionary: Option<String>,
+    // If the word is never accepted or suggested
+    pub forbidden: bool,
+}
+
+impl Stava {
+    /// Statistics of the learned words, with the `top` most frequent words
+    pub fn stats(&self, top: usize) -> Stats {
+        let mut words: Vec<(&str, u32)> = self.words().collect();
+        words.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let mut length_distribution = BTreeMap::new();
+        let mut characters = BTreeSet::new();
+        for (word, _) in &words {
+            *length_distribution.entry(word.chars().count()).or_default() += 1;
+            characters.extend(word.chars());
+        }
+
+        let singletons = words.iter().filter(|(_, count)| *count == 1).count();
+        let is_in_alphabet = |c: &char| self.alphabet.iter().any(|letter| letter.starts_with(*c));
+        Stats {
+            total_count: self.total_count,
+            unique_words: words.len(),
+            top_words: words
+                .iter()
+                .take(top)
+                .map(|&(word, count)| WordCount {
+                    word: word.to_string(),
+                    count,
+                })
+                .collect(),
+            length_distribution,
+            singleton_ratio: match words.is_empty() {
+                true => 0.0,
+                false => singletons as f64 / words.len() as f64,
+            },
+            missing_letters: self
+                .alphabet
+                .iter()
+                .filter(|letter| letter.chars().all(|c| !characters.contains(&c)))
+                .cloned()
+                .collect(),
+            other_characters: characters
+                .iter()
+                .copied()
+                .filter(|c| !is_in_alphabet(c))
+                .collect(),
+        }
+    }
+
+    /// The count and rank of a word
+    pub fn lookup(&self, word: &str) -> Lookup {
+        let word = normalize(&word.to_lowercase());
+        let count = self.count(&word);
+        let rank = count.map(|count| {
+            1 + self
+                .words_w_count
+                .values()
+                .filter(|&&other| other > count)
+                .count()
+        });
+        Lookup {
+            count,
+            rank,
+            dictionary: self
+                .dictionary_of(&word)
+                .map(|dictionary| dictionary.name.clone()),
+            forbidden: self.forbidden.contains(&word),
+            word,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tokenizer::UnicodeTokenizer;
+
+    fn stava() -> Stava {
+        let mut stava = Stava::default();
+        stava.learn("the cat and the dog and the cafe");
+        stava
+    }
+
+    #[test]
+    fn test_stats() {
+        let actual = stava().stats(2);
+
+        assert_eq!(actual.total_count, 8);
+        assert_eq!(actual.unique_words, 5);
+        assert_eq!(
+            actual.top_words,
+            vec![
+                WordCount {
+                    word: "the".to_string(),
+                    count: 3
+                },
+                WordCount {
+                    word: "and".to_string(),
+                    count: 2
+                }
+            ]
+        );
+        assert_eq!(
+            actual.length_distribution,
+            BTreeMap::from_iter([(3, 4), (4, 1)])
+        );
+        assert_eq!(actual.singleton_ratio, 0.6);
+        assert_eq!(actual.missing_letters.len(), 26 - 10);
+        assert!(!actual.missing_letters.contains(&"c".to_string()));
+        assert!(actual.other_characters.is_empty());
+    }
+
+    #[test]
+    fn test_stats_of_unicode_words() {
+        let mut stava = Stava::builder()
+            .tokenizer(UnicodeTokenizer)
+            .build()
+            .unwrap();
+        stava.learn("café");
+
+        assert_eq!(stava.stats(0).other_characters, vec!['é']);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let stava = stava();
+
+        let actual = stava.lookup("AND");
+        let expected = Lookup {
+            word: "and".to_string(),
+            count: Some(2),
+            rank: Some(2),
+            dictionary: None,
+            forbidden: false,
+        };
+        assert_eq!(actual, expected);
+        assert_eq!(stava.lookup("cat").rank, Some(3));
+        assert_eq!(stava.lookup("dog").rank, Some(3));
+        assert_eq!(stava.lookup("bird").count, None);
+        assert_eq!(stava.lookup("bird").rank, None);
+    }
+}
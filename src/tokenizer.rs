@@ -0,0 +1,122 @@
+use regex::Regex;
+use serde::Deserialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+use std::str::FromStr;
+
+lazy_static! {
+    static ref ASCII_WORD: Regex = Regex::new(r"[a-z]+(?:['\x{2019}][a-z]+)*").unwrap();
+}
+
+/// Splits text into the words that are learned and checked.
+///
+/// The text is lowercased before it is tokenized.
+pub trait Tokenizer: Send + Sync {
+    /// The words in `text` with the byte offset each word starts at
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)>;
+}
+
+/// Words of the letters a-z with apostrophes within them, like "don't". Everything else separates
+/// words (default)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiTokenizer;
+
+impl Tokenizer for AsciiTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        ASCII_WORD.tokenize(text)
+    }
+}
+
+/// Words as defined by Unicode word segmentation (UAX #29).
+///
+/// Keeps non-ASCII letters, digits and apostrophes within words, e.g. "don't", "mp3" and "café".
+/// Words without any letters, like numbers, are skipped.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnicodeTokenizer;
+
+impl Tokenizer for UnicodeTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        text.unicode_word_indices()
+            .filter(|(_, word)| word.chars().any(char::is_alphabetic))
+            .collect()
+    }
+}
+
+/// Every match of the regex is a word
+impl Tokenizer for Regex {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        self.find_iter(text)
+            .map(|m| (m.start(), m.as_str()))
+            .collect()
+    }
+}
+
+/// The built-in tokenizers
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    Ascii,
+    Unicode,
+}
+
+impl FromStr for TokenizerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(TokenizerKind::Ascii),
+            "unicode" => Ok(TokenizerKind::Unicode),
+            _ => Err(format!("Unsupported tokenizer [{}]", s)),
+        }
+    }
+}
+
+impl TokenizerKind {
+    pub fn tokenizer(&self) -> Box<dyn Tokenizer> {
+        match self {
+            TokenizerKind::Ascii => Box::new(AsciiTokenizer),
+            TokenizerKind::Unicode => Box::new(UnicodeTokenizer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(tokenizer: &dyn Tokenizer, text: &str) -> Vec<String> {
+        tokenizer
+            .tokenize(text)
+            .into_iter()
+            .map(|(_, word)| word.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii_tokenizer() {
+        let actual = words(&AsciiTokenizer, "don't buy the mp3, café-au-lait 3000");
+        let expected = vec!["don't", "buy", "the", "mp", "caf", "au", "lait"];
+        assert_eq!(actual, expected);
+
+        let actual = words(&AsciiTokenizer, "'quoted' they\u{2019}re rock'n'roll");
+        let expected = vec!["quoted", "they\u{2019}re", "rock'n'roll"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_unicode_tokenizer() {
+        let actual = words(&UnicodeTokenizer, "don't buy the mp3, café-au-lait 3000");
+        let expected = vec!["don't", "buy", "the", "mp3", "café", "au", "lait"];
+        assert_eq!(actual, expected);
+
+        let actual = UnicodeTokenizer.tokenize("så är det");
+        assert_eq!(actual, vec![(0, "så"), (4, "är"), (8, "det")]);
+    }
+
+    #[test]
+    fn test_regex_tokenizer() {
+        let regex = Regex::new(r"[a-z]+(?:-[a-z]+)*").unwrap();
+        let actual = words(&regex, "a well-known co-op");
+        assert_eq!(actual, vec!["a", "well-known", "co-op"]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use crate::error::{Result, StavaError};
+use crate::{normalize, read_chunks, walk, Stava};
+
+impl Stava {
+    /// Remove a learned word and its count. Returns false if it was not learned
+    pub fn forget(&mut self, word: &str) -> bool {
+        let word = normalize(&word.to_lowercase());
+        let is_learned = self.words_w_count.contains_key(&word);
+        self.set_count(word, 0);
+        is_learned
+    }
+
+    /// Subtract one from the count of a word for each time it is in `text`, undoing
+    /// [`learn`](Stava::learn). Words whose count reaches 0 are removed.
+    ///
+    /// The weights of the dictionaries the words were learned from are not applied.
+    pub fn unlearn(&mut self, text: &str) {
+        let words: Vec<String> = self
+            .tokenizer
+            .tokenize(&text.to_lowercase())
+            .into_iter()
+            .map(|(_, word)| normalize(word))
+            .collect();
+        for word in words {
+            let count = self.count_of(&word).saturating_sub(1);
+            self.set_count(word, count);
+        }
+    }
+
+    /// Unlearn the words in the file at `path`, which is read in chunks. Binary files are skipped.
+    pub fn unlearn_from_path(&mut self, path: &Path) -> Result<()> {
+        if let Some(reader) = walk::open_text_file(path)? {
+            read_chunks(reader, |text| self.unlearn(text))
+                .map_err(|err| StavaError::io(path, err))?;
+        }
+        Ok(())
+    }
+
+    /// Multiply every count by `factor`, between 0 and 1, so that words learned long ago count
+    /// less than recently learned ones. Counts are rounded and words whose count reaches 0 are
+    /// removed. Returns the number of removed words, or an error if `factor` is not between 0 and
+    /// 1.
+    pub fn decay(&mut self, factor: f64) -> Result<usize> {
+        if !(0.0..=1.0).contains(&factor) {
+            return Err(StavaError::invalid_setting(
+                "factor",
+                format!("Expected a number between 0 and 1 [{}]", factor),
+            ));
+        }
+        let counts: Vec<(String, u32)> = self
+            .words_w_count
+            .iter()
+            .map(|(word, &count)| (word.clone(), (f64::from(count) * factor).round() as u32))
+            .collect();
+
+        let mut removed = 0;
+        for (word, count) in counts {
+            if count == 0 {
+                removed += 1;
+            }
+            self.set_count(word, count);
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dictionary::Dictionary;
+
+    #[test]
+    fn test_forget() {
+        let mut stava = Stava::default();
+        stava.learn_as(&Dictionary::new("docs"), "the cat and the dog");
+
+        assert!(stava.forget("Cat"));
+        assert!(!stava.forget("cat"));
+        assert!(!stava.contains("cat"));
+        assert_eq!(stava.dictionary_of("cat"), None);
+        assert_eq!(stava.total_count, 4);
+        assert_ne!(stava.correct("cet").unwrap().word, "cat");
+    }
+
+    #[test]
+    fn test_forget_removes_phonetic_match() {
+        let mut stava = Stava::default();
+        stava.learn("phone");
+        stava.forget("phone");
+
+        assert!(!stava.correct("fone").unwrap().was_corrected);
+    }
+
+    #[test]
+    fn test_unlearn() {
+        let mut stava = Stava::default();
+        stava.learn("the cat and the dog");
+
+        stava.unlearn("The cat");
+
+        assert_eq!(stava.count("the"), Some(1));
+        assert_eq!(stava.count("cat"), None);
+        assert_eq!(stava.count("dog"), Some(1));
+        assert_eq!(stava.total_count, 3);
+
+        stava.unlearn("bird");
+        assert_eq!(stava.count("bird"), None);
+        assert_eq!(stava.total_count, 3);
+    }
+
+    #[test]
+    fn test_unlearn_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        std::fs::write(&path, "The cat\nthe").unwrap();
+        let mut stava = Stava::default();
+        stava.learn("the the the cat dog");
+
+        stava.unlearn_from_path(&path).unwrap();
+
+        assert_eq!(stava.count("the"), Some(1));
+        assert_eq!(stava.count("cat"), None);
+        assert_eq!(stava.count("dog"), Some(1));
+    }
+
+    #[test]
+    fn test_decay() {
+        let mut stava = Stava::default();
+        stava.learn("the the the the cat and and");
+
+        assert_eq!(stava.decay(0.5).unwrap(), 0);
+        assert_eq!(stava.count("the"), Some(2));
+        assert_eq!(stava.count("and"), Some(1));
+        assert_eq!(stava.count("cat"), Some(1));
+
+        assert_eq!(stava.decay(0.4).unwrap(), 2);
+        assert_eq!(stava.count("the"), Some(1));
+        assert_eq!(stava.len(), 1);
+        assert_eq!(stava.total_count, 1);
+    }
+
+    #[test]
+    fn test_decay_invalid_factor() {
+        let mut stava = Stava::default();
+        stava.learn("the the cat");
+
+        for factor in [f64::NAN, -0.5, 1.5, f64::INFINITY] {
+            let actual = stava.decay(factor);
+
+            assert!(matches!(actual, Err(StavaError::InvalidSetting { .. })));
+        }
+        assert_eq!(stava.count("the"), Some(2));
+    }
+}
@@ -0,0 +1,231 @@
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, StavaError};
+
+pub const IGNORE_FILE_NAME: &str = ".stavaignore";
+
+// The number of bytes inspected when deciding if a file is binary
+const BINARY_CHECK_LEN: usize = 8000;
+// The size of the buffer used when streaming files
+const READ_BUFFER_LEN: usize = 64 * 1024;
+
+/// Glob patterns deciding which files to use when walking directories
+#[derive(Debug, Default)]
+pub struct FileFilter {
+    // Only use files matching any of these patterns, unless empty
+    pub include: Vec<String>,
+    // Never use files matching any of these patterns
+    pub exclude: Vec<String>,
+}
+
+/// The files in `paths`, where directories are walked recursively.
+///
+/// Files ignored by `.gitignore` or `.stavaignore` files, hidden files and files not passing
+/// `filter` are skipped. Paths given explicitly are always used.
+pub fn walk(paths: &[PathBuf], filter: &FileFilter) -> Result<Vec<PathBuf>> {
+    let mut result = Vec::new();
+    for path in paths {
+        let mut overrides = OverrideBuilder::new(path);
+        for pattern in &filter.include {
+            overrides
+                .add(pattern)
+                .map_err(|err| StavaError::invalid_pattern(pattern, err))?;
+        }
+        for pattern in &filter.exclude {
+            overrides
+                .add(&format!("!{}", pattern))
+                .map_err(|err| StavaError::invalid_pattern(pattern, err))?;
+        }
+        let overrides = overrides.build().map_err(|err| walk_error(path, err))?;
+
+        let walker = WalkBuilder::new(path)
+            .require_git(false)
+            .add_custom_ignore_filename(IGNORE_FILE_NAME)
+            .overrides(overrides)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        for entry in walker {
+            let entry = entry.map_err(|err| walk_error(path, err))?;
+            if entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                result.push(entry.into_path());
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// The paths matching `pattern`. A pattern that is an existing path matches only itself.
+pub fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>> {
+    if Path::new(pattern).exists() {
+        return Ok(vec![PathBuf::from(pattern)]);
+    }
+
+    let paths = glob::glob(pattern).map_err(|err| StavaError::invalid_pattern(pattern, err))?;
+    let mut result = Vec::new();
+    for path in paths {
+        let path = path.map_err(|err| StavaError::io(err.path().to_path_buf(), err.into()))?;
+        result.push(path);
+    }
+    Ok(result)
+}
+
+/// Read the contents of a text file. Returns `None` if the file is binary.
+pub fn read_text_file(path: &Path) -> Result<Option<String>> {
+    let bytes = fs::read(path).map_err(|err| StavaError::io(path, err))?;
+    if is_binary(&bytes) {
+        return Ok(None);
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) => Ok(Some(String::from_utf8_lossy(err.as_bytes()).into_owned())),
+    }
+}
+
+/// Open a text file for streaming. Returns `None` if the file is binary.
+pub fn open_text_file(path: &Path) -> Result<Option<BufReader<File>>> {
+    let file = File::open(path).map_err(|err| StavaError::io(path, err))?;
+    let mut reader = BufReader::with_capacity(READ_BUFFER_LEN, file);
+    let start = reader.fill_buf().map_err(|err| StavaError::io(path, err))?;
+    if is_binary(start) {
+        return Ok(None);
+    }
+    Ok(Some(reader))
+}
+
+fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_CHECK_LEN).any(|&byte| byte == 0)
+}
+
+fn walk_error(path: &Path, err: ignore::Error) -> StavaError {
+    let message = err.to_string();
+    match err.into_io_error() {
+        Some(source) => StavaError::io(path, source),
+        None => StavaError::io(path, io::Error::other(message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_files(dir: &Path, files: &[(&str, &[u8])]) {
+        for (name, contents) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    fn relative(dir: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| path.strip_prefix(dir).unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_walk_honors_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[
+                (".gitignore", b"target/\n"),
+                (".stavaignore", b"*.lock\n"),
+                ("README.md", b"readme"),
+                ("Cargo.lock", b"lock"),
+                ("src/lib.rs", b"lib"),
+                ("target/debug/out.txt", b"out"),
+            ],
+        );
+
+        let actual = walk(&[dir.path().to_path_buf()], &FileFilter::default()).unwrap();
+
+        assert_eq!(
+            relative(dir.path(), actual),
+            vec!["README.md", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn test_walk_with_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[
+                ("README.md", b"readme"),
+                ("docs/guide.md", b"guide"),
+                ("docs/draft.md", b"draft"),
+                ("src/lib.rs", b"lib"),
+            ],
+        );
+        let filter = FileFilter {
+            include: vec!["*.md".to_string()],
+            exclude: vec!["draft.md".to_string()],
+        };
+
+        let actual = walk(&[dir.path().to_path_buf()], &filter).unwrap();
+
+        assert_eq!(
+            relative(dir.path(), actual),
+            vec!["README.md", "docs/guide.md"]
+        );
+    }
+
+    #[test]
+    fn test_read_text_file_skips_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[("text.txt", b"text"), ("image.png", b"\x89PNG\x00\x01")],
+        );
+
+        let actual = read_text_file(&dir.path().join("text.txt")).unwrap();
+        assert_eq!(actual, Some("text".to_string()));
+
+        let actual = read_text_file(&dir.path().join("image.png")).unwrap();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn test_open_text_file_skips_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[("text.txt", b"text"), ("image.png", b"\x89PNG\x00\x01")],
+        );
+
+        let mut text = String::new();
+        let mut reader = open_text_file(&dir.path().join("text.txt"))
+            .unwrap()
+            .unwrap();
+        io::Read::read_to_string(&mut reader, &mut text).unwrap();
+        assert_eq!(text, "text");
+
+        let actual = open_text_file(&dir.path().join("image.png")).unwrap();
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn test_expand_glob() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[("a.txt", b"a"), ("b.txt", b"b"), ("c.md", b"c")],
+        );
+
+        let pattern = format!("{}/*.txt", dir.path().display());
+        let actual = expand_glob(&pattern).unwrap();
+
+        assert_eq!(relative(dir.path(), actual), vec!["a.txt", "b.txt"]);
+    }
+}
@@ -1,15 +1,29 @@
 #![allow(non_snake_case)]
 mod cli {
     use assert_cmd::prelude::*;
+    use predicates::prelude::*;
     use predicates::str::{contains, ends_with};
+    use std::fs;
     use std::io::Write;
+    use std::path::Path;
     use std::process::Command;
 
     type TestResult = Result<(), Box<dyn std::error::Error>>;
 
+    // Runs stava in `dir` with `dir` as the home directory, so that the personal dictionary and
+    // config of the machine the tests run on are not used
+    fn stava(dir: &Path) -> Result<Command, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("stava")?;
+        cmd.current_dir(dir)
+            .env("HOME", dir)
+            .env("XDG_DATA_HOME", dir);
+        Ok(cmd)
+    }
+
     #[test]
     fn test_output_ends_with_newline() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("speling");
 
         cmd.assert().success().stdout(ends_with("\n"));
@@ -18,7 +32,8 @@ mod cli {
 
     #[test]
     fn test_returns_match__when_match_is_in_default_words() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         // I know this word is in the default set of words (by manually checking)
         cmd.arg("speling");
 
@@ -28,10 +43,11 @@ mod cli {
 
     #[test]
     fn test_returns_match__when_match_is_in_file() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file = tempfile::NamedTempFile::new()?;
         tmp_file.write_all("spelling, and some other words".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("speling");
         cmd.arg(tmp_file.path());
 
@@ -41,12 +57,13 @@ mod cli {
 
     #[test]
     fn test_returns_match__when_match_is_in_any_of_files() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file1 = tempfile::NamedTempFile::new()?;
         tmp_file1.write_all("no match in this file".as_bytes())?;
         let mut tmp_file2 = tempfile::NamedTempFile::new()?;
         tmp_file2.write_all("but a match in this file - spelling".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("speling");
         cmd.arg(tmp_file1.path());
         cmd.arg(tmp_file2.path());
@@ -55,12 +72,41 @@ mod cli {
         Ok(())
     }
 
+    #[test]
+    fn test_returns_contraction__when_apostrophe_is_missing() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
+        cmd.arg("doesnt");
+
+        cmd.assert().success().stdout("doesn't\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__accepts_typographic_apostrophes() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "we don't know")?;
+        fs::write(
+            project_dir.path().join("README.md"),
+            "We don\u{2019}t know, we dont",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "README.md", "--learn", "words.txt"]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("README.md:1:19: dont -> don't\n");
+        Ok(())
+    }
+
     #[test]
     fn test_returns_input_word__when_no_match__with_file() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file = tempfile::NamedTempFile::new()?;
         tmp_file.write_all("no match in this file".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("inputword");
         cmd.arg(tmp_file.path());
 
@@ -70,7 +116,8 @@ mod cli {
 
     #[test]
     fn test_returns_input_word__when_no_match__with_default_words() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         // I know this word is NOT in the default set of words (by manually checking)
         cmd.arg("quintessential");
 
@@ -80,10 +127,11 @@ mod cli {
 
     #[test]
     fn test_returns_match__when_uppercase_word_in_file() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file = tempfile::NamedTempFile::new()?;
         tmp_file.write_all("SPELLING".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("speling");
         cmd.arg(tmp_file.path());
 
@@ -93,7 +141,8 @@ mod cli {
 
     #[test]
     fn test_exits__when_non_existing_file() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("speling");
         cmd.arg("some_non_existing_file");
 
@@ -105,7 +154,8 @@ mod cli {
 
     #[test]
     fn test_exits__when_missing_word_arg() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
 
         cmd.assert().failure().stderr(contains(
             "The following required arguments were not provided:\n    <WORD>",
@@ -115,10 +165,11 @@ mod cli {
 
     #[test]
     fn test_returns_match_found_in_default_words__with_file_and_default_flag() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file = tempfile::NamedTempFile::new()?;
         tmp_file.write_all("no match in this file".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--default");
         cmd.arg("speling");
         cmd.arg(tmp_file.path());
@@ -129,10 +180,11 @@ mod cli {
 
     #[test]
     fn test_returns_match_found_in_file__with_default_flag() -> TestResult {
+        let dir = tempfile::tempdir()?;
         let mut tmp_file = tempfile::NamedTempFile::new()?;
         tmp_file.write_all("quintessential is not included in default words".as_bytes())?;
 
-        let mut cmd = Command::cargo_bin("stava")?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--default");
         cmd.arg("ruintessential");
         cmd.arg(tmp_file.path());
@@ -143,7 +195,8 @@ mod cli {
 
     #[test]
     fn test_returns_exit_code_1__when_word_was_corrected__with_exit_code_flag() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--exit-code");
         cmd.arg("speling");
 
@@ -153,7 +206,8 @@ mod cli {
 
     #[test]
     fn test_returns_exit_code_0__when_word_was_not_corrected__with_exit_code_flag() -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--exit-code");
         cmd.arg("spelling");
 
@@ -164,7 +218,8 @@ mod cli {
     #[test]
     fn test_returns_only_exit_code_1__when_word_was_corrected__with_exit_code_only_flag(
     ) -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--exit-code-only");
         cmd.arg("speling");
 
@@ -175,11 +230,976 @@ mod cli {
     #[test]
     fn test_returns_only_exit_code_0__when_word_was_not_corrected__with_exit_code_only_flag(
     ) -> TestResult {
-        let mut cmd = Command::cargo_bin("stava")?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
         cmd.arg("--exit-code-only");
         cmd.arg("spelling");
 
         cmd.assert().success().stdout("");
         Ok(())
     }
+
+    #[test]
+    fn test_dict_list__lists_added_words() -> TestResult {
+        let dict_dir = tempfile::tempdir()?;
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "add", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.args(["rustacean", "Clippy"]);
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "list", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+
+        cmd.assert().success().stdout("clippy\nrustacean\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_import__imports_word_lists() -> TestResult {
+        let dict_dir = tempfile::tempdir()?;
+        let lists_dir = tempfile::tempdir()?;
+        fs::write(
+            lists_dir.path().join(".aspell.en.pws"),
+            "personal_ws-1.1 en 1\nKubernetes\n",
+        )?;
+        fs::write(lists_dir.path().join("en.utf-8.add"), "rustacean\nalot/!\n")?;
+        fs::write(
+            lists_dir.path().join("words.txt"),
+            "personal_ws-1.1 en 1\nferris\n",
+        )?;
+
+        let mut cmd = stava(lists_dir.path())?;
+        cmd.args([
+            "dict",
+            "import",
+            ".aspell.en.pws",
+            "en.utf-8.add",
+            "--dict-dir",
+        ]);
+        cmd.arg(dict_dir.path());
+        cmd.assert().success();
+
+        let mut cmd = stava(lists_dir.path())?;
+        cmd.args([
+            "dict",
+            "import",
+            "words.txt",
+            "--from",
+            "aspell-pws",
+            "--dict-dir",
+        ]);
+        cmd.arg(dict_dir.path());
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "list", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.assert()
+            .success()
+            .stdout("ferris\nkubernetes\nrustacean\n");
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "list", "--bad", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.assert().success().stdout("alot\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__reports_bad_words_in_personal_dict() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("words.txt"),
+            "we like it a lot alot",
+        )?;
+        fs::write(project_dir.path().join("README.md"), "We like it alot")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "add", "--bad", "alot", "--dict-dir"]);
+        cmd.arg(project_dir.path().join("dict"));
+        cmd.assert().success();
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "check",
+            "README.md",
+            "--learn",
+            "words.txt",
+            "--dict-dir",
+            "dict",
+        ]);
+        cmd.assert()
+            .code(1)
+            .stdout(contains("README.md:1:12: alot"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_input_word__when_word_is_in_personal_dict() -> TestResult {
+        let dict_dir = tempfile::tempdir()?;
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "add", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.arg("speling");
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.arg("--dict-dir");
+        cmd.arg(dict_dir.path());
+        cmd.arg("--exit-code");
+        cmd.arg("speling");
+
+        cmd.assert().success().stdout("speling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_does_not_return_match__when_match_is_in_ignore_list() -> TestResult {
+        let dict_dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all("spelling".as_bytes())?;
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "add", "--ignore", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.arg("spelling");
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.arg("--dict-dir");
+        cmd.arg(dict_dir.path());
+        cmd.arg("speling");
+        cmd.arg(tmp_file.path());
+
+        cmd.assert().success().stdout("speling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_remove__removes_word() -> TestResult {
+        let dict_dir = tempfile::tempdir()?;
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "add", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.args(["rustacean", "clippy"]);
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "remove", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+        cmd.arg("clippy");
+        cmd.assert().success();
+
+        let mut cmd = stava(dict_dir.path())?;
+        cmd.args(["dict", "list", "--dict-dir"]);
+        cmd.arg(dict_dir.path());
+
+        cmd.assert().success().stdout("rustacean\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match__when_match_is_in_config_dictionary() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::create_dir_all(project_dir.path().join("docs/nested"))?;
+        fs::write(project_dir.path().join("docs/glossary.txt"), "kubernetes")?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            r#"dictionaries = ["docs/glossary.txt"]"#,
+        )?;
+
+        let mut cmd = stava(&project_dir.path().join("docs/nested"))?;
+        cmd.arg("kubernets");
+
+        cmd.assert().success().stdout("kubernetes\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_input_word__when_correction_is_below_min_confidence() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all("the the the the rust".as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.arg("rustc").arg(tmp_file.path());
+        cmd.assert().success().stdout("rust\n");
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--min-confidence", "0.4", "rustc"])
+            .arg(tmp_file.path());
+        cmd.assert().success().stdout("rustc\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_more_frequent_match__with_combined_ranking() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all(format!("spewing {}", "sapling ".repeat(1000)).as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.arg("speling").arg(tmp_file.path());
+        cmd.assert().success().stdout("spewing\n");
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--ranking", "combined", "speling"])
+            .arg(tmp_file.path());
+        cmd.assert().success().stdout("sapling\n");
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args([
+            "--ranking",
+            "combined",
+            "--edit-penalty",
+            "10000",
+            "speling",
+        ]);
+        cmd.arg(tmp_file.path());
+        cmd.assert().success().stdout("spewing\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_more_frequent_match__with_edit_penalty() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all(format!("spewing {}", "sapling ".repeat(1000)).as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--edit-penalty", "10", "speling"])
+            .arg(tmp_file.path());
+        cmd.assert().success().stdout("sapling\n");
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--ranking", "norvig", "--edit-penalty", "10", "speling"])
+            .arg(tmp_file.path());
+        cmd.assert()
+            .code(2)
+            .stderr("error: Invalid edit-penalty: Only the combined ranking has an edit penalty\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_explains_correction__with_explain_flag() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all("spelling spelling spewing".as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--explain", "speling"]).arg(tmp_file.path());
+        cmd.assert().success().stdout(
+            "speling -> spelling\n\
+             Rule: the most frequent of the candidates with the fewest edits\n\
+             Confidence: 0.363\n\
+             Distance: 1\n\
+             Edits:\n  insert 'l' at 4\n\
+             Candidates:\n  spelling: count 2, distance 1\n  spewing: count 1, distance 1\n",
+        );
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["--explain", "-f", "json", "spelng"])
+            .arg(tmp_file.path());
+        cmd.assert()
+            .success()
+            .stdout(contains(r#""input":"spelng","word":"spelling""#))
+            .stdout(contains(r#""rule":"most_frequent","distance":2"#))
+            .stdout(contains(
+                r#""edits":[{"op":"insert","position":4,"letter":"l"},{"op":"insert","position":5,"letter":"i"}]"#,
+            ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_json__with_config_format() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.md"), "spelling")?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            "files = [\"*.md\"]\nformat = \"json\"",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("speling");
+
+        cmd.assert()
+            .success()
+            .stdout("{\"word\":\"spelling\",\"was_corrected\":true,\"confidence\":0.5}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match_from_weighted_dictionary() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "kitten kitten kitten")?;
+        fs::write(project_dir.path().join("glossary.txt"), "kitted")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["kittex", "words.txt", "glossary.txt:10", "--format", "json"]);
+
+        cmd.assert().success().stdout(
+            "{\"word\":\"kitted\",\"was_corrected\":true,\"dictionary\":\"glossary.txt\",\"confidence\":0.418}\n",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match_from_config_dictionary_with_priority() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "kitten kitten kitten")?;
+        fs::write(project_dir.path().join("glossary.txt"), "kitted")?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            r#"dictionaries = ["words.txt", "glossary.txt:1:1"]"#,
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("kittex");
+
+        cmd.assert().success().stdout("kitted\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match__when_match_is_in_frequency_list() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("counts.tsv"),
+            "word\tcount\nkitten\t200\nkitted\t50\n",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["kitted", "--frequency-list", "counts.tsv"]);
+        cmd.assert().success().stdout("kitted\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "kitted",
+            "--frequency-list",
+            "counts.tsv",
+            "--min-count",
+            "100",
+        ]);
+        cmd.assert().success().stdout("kitten\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match__with_frequency_list_and_files() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "kitted kitted kitted")?;
+        fs::write(project_dir.path().join("counts.csv"), "kitten,2\n")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["kittex", "words.txt", "--frequency-list", "counts.csv"]);
+        cmd.assert().success().stdout("kitted\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["kittex", "words.txt", "--frequency-list", "counts.csv"]);
+        cmd.args(["--count-scale", "2"]);
+        cmd.assert().success().stdout("kitten\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_flag_overrides_config() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "poetry")?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            "dictionaries = [\"words.txt\"]\nmax-distance = 1",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("boetr");
+        cmd.assert().success().stdout("boetr\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["--max-distance", "2", "boetr"]);
+        cmd.assert().success().stdout("poetry\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_input_word__when_word_matches_config_ignore_pattern() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            r#"ignore = ["^[A-Z]+$"]"#,
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["--exit-code", "SPELING"]);
+
+        cmd.assert().success().stdout("SPELING\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match__when_match_is_in_directory() -> TestResult {
+        let words_dir = tempfile::tempdir()?;
+        fs::create_dir_all(words_dir.path().join("nested"))?;
+        fs::write(words_dir.path().join("nested/words.txt"), "spelling")?;
+
+        let mut cmd = stava(words_dir.path())?;
+        cmd.arg("speling");
+        cmd.arg(words_dir.path());
+
+        cmd.assert().success().stdout("spelling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_match__when_match_is_in_glob_pattern() -> TestResult {
+        let words_dir = tempfile::tempdir()?;
+        fs::write(words_dir.path().join("words.txt"), "spelling")?;
+
+        let mut cmd = stava(words_dir.path())?;
+        cmd.arg("speling");
+        cmd.arg(format!("{}/*.txt", words_dir.path().display()));
+
+        cmd.assert().success().stdout("spelling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__reports_misspelled_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("words.txt"),
+            "the spelling of words",
+        )?;
+        fs::write(
+            project_dir.path().join("README.md"),
+            "The speling\nof wurds",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "--learn", "words.txt"]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("./README.md:1:5: speling -> spelling\n./README.md:2:4: wurds -> words\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__reports_dictionary__with_json_format() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the spelling")?;
+        fs::write(project_dir.path().join("README.md"), "The speling")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "check",
+            "README.md",
+            "--learn",
+            "words.txt",
+            "--format",
+            "json",
+        ]);
+
+        cmd.assert().code(1).stdout(concat!(
+            r#"{"path":"README.md","line":1,"column":5,"word":"speling","suggestion":"spelling","#,
+            r#""dictionary":"words.txt"}"#,
+            "\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__skips_ignored_and_binary_files() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::create_dir_all(project_dir.path().join("target"))?;
+        fs::create_dir_all(project_dir.path().join("vendor"))?;
+        fs::write(
+            project_dir.path().join("words.txt"),
+            "the spelling of words",
+        )?;
+        fs::write(project_dir.path().join(".gitignore"), "target/")?;
+        fs::write(project_dir.path().join(".stavaignore"), "vendor/")?;
+        fs::write(project_dir.path().join("target/out.txt"), "speling")?;
+        fs::write(project_dir.path().join("vendor/lib.txt"), "speling")?;
+        fs::write(project_dir.path().join("image.png"), b"speling\x00")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", ".", "--learn", "words.txt"]);
+
+        cmd.assert().success().stdout("");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__with_include_and_exclude() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::create_dir_all(project_dir.path().join("docs"))?;
+        fs::write(
+            project_dir.path().join("words.txt"),
+            "the spelling of words",
+        )?;
+        fs::write(project_dir.path().join("docs/guide.md"), "speling")?;
+        fs::write(project_dir.path().join("docs/draft.md"), "wurds")?;
+        fs::write(project_dir.path().join("src.rs"), "wurds")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "--learn", "words.txt"]);
+        cmd.args(["--include", "*.md", "--exclude", "draft.md"]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("./docs/guide.md:1:1: speling -> spelling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__with_unicode_tokenizer() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the café is open")?;
+        fs::write(project_dir.path().join("README.md"), "The café is opn")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "README.md", "--learn", "words.txt"]);
+        cmd.args(["--tokenizer", "unicode"]);
+
+        cmd.assert().code(1).stdout("README.md:1:13: opn -> open\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__with_token_regex() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "a well-known word")?;
+        fs::write(project_dir.path().join("README.md"), "A well-knwn word")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "README.md", "--learn", "words.txt"]);
+        cmd.args(["--token-regex", "[a-z]+(-[a-z]+)*"]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("README.md:1:3: well-knwn -> well-known\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_replacement__when_word_is_known() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the teh kubernetes")?;
+        fs::write(
+            project_dir.path().join("typos.txt"),
+            "teh -> the\nkuberentes -> kubernetes\n",
+        )?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            "dictionaries = [\"words.txt\"]\nreplacements = [\"typos.txt\"]",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("teh");
+        cmd.assert().success().stdout("the\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["--replacements", "typos.txt", "kuberentes", "words.txt"]);
+        cmd.assert().success().stdout("kubernetes\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__reports_forbidden_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("words.txt"),
+            "we like it a lot alot",
+        )?;
+        fs::write(project_dir.path().join("forbidden.txt"), "alot -> a lot\n")?;
+        fs::write(project_dir.path().join("README.md"), "We like it alot")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "README.md", "--learn", "words.txt"]);
+        cmd.args(["--forbidden", "forbidden.txt"]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("README.md:1:12: alot -> a lot\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_returns_inflected_match__with_hunspell_dictionary() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let dic = format!(
+            "{}/tests/fixtures/hunspell/en.dic",
+            env!("CARGO_MANIFEST_DIR")
+        );
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["unhapiness", "--hunspell", &dic, "--format", "json"]);
+
+        cmd.assert().success().stdout(format!(
+            "{{\"word\":\"unhappiness\",\"was_corrected\":true,\"dictionary\":\"{}\",\"confidence\":0.5}}\n",
+            dic
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_check__with_hunspell_dictionary() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        let dic = format!(
+            "{}/tests/fixtures/hunspell/en.dic",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        fs::write(
+            project_dir.path().join("notes.md"),
+            "Walking, cryed\nunhappiness",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["check", "notes.md", "--hunspell", &dic]);
+
+        cmd.assert()
+            .code(1)
+            .stdout("notes.md:1:10: cryed -> cried\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_export__writes_learned_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the cat and the dog")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["export", "--learn", "words.txt", "--dict-dir", "."]);
+        cmd.assert()
+            .success()
+            .stdout("the\t2\nand\t1\ncat\t1\ndog\t1\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["export", "--learn", "words.txt", "--dict-dir", "."]);
+        cmd.args(["--to", "aspell-pws", "--sort", "alphabet"]);
+        cmd.assert()
+            .success()
+            .stdout("personal_ws-1.1 en 4\nand\ncat\ndog\nthe\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["export", "--learn", "words.txt", "--dict-dir", "."]);
+        cmd.args(["--to", "hunspell", "--min-frequency", "2"]);
+        cmd.assert().success().stdout("1\nthe\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_export__exits_with_error__with_output_format() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the cat")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "-f",
+            "json",
+            "export",
+            "--learn",
+            "words.txt",
+            "--dict-dir",
+            ".",
+        ]);
+        cmd.assert().code(2).stdout("").stderr(
+            "error: Invalid format: The exported words are written in the format given by --to\n",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats__reports_learned_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the cat and the dog")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "stats",
+            "--learn",
+            "words.txt",
+            "--dict-dir",
+            ".",
+            "--top",
+            "1",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains("Total words: 5\nUnique words: 4\n"))
+            .stdout(contains("Singleton ratio: 0.750"))
+            .stdout(contains("Top words:\n  1. the 2\nWord lengths:\n  3: 4\n"));
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "stats",
+            "--learn",
+            "words.txt",
+            "--dict-dir",
+            ".",
+            "-f",
+            "json",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains(r#""top_words":[{"word":"the","count":2}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lookup__shows_count_and_rank() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.txt"), "the cat and the dog")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["lookup", "cat", "--learn", "words.txt", "--dict-dir", "."]);
+        cmd.assert()
+            .success()
+            .stdout("cat: count 1, rank 2 of 4, from words.txt\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["lookup", "bird", "--learn", "words.txt", "--dict-dir", "."]);
+        cmd.assert().code(1).stdout("bird: not learned\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval__reports_accuracy_on_sample_set() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        let fixtures = format!("{}/tests/fixtures/eval", env!("CARGO_MANIFEST_DIR"));
+        let testset = format!("{}/testset.txt", fixtures);
+        let corpus = format!("{}/corpus.txt", fixtures);
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["eval", &testset, "--learn", &corpus, "--dict-dir", "."]);
+        cmd.assert()
+            .success()
+            .stdout(contains(
+                "Words: 26\nAccuracy: 92.3% (24 correct)\nUnknown: 3.8% (1 not learned)\n",
+            ))
+            .stdout(contains("Words per second: "))
+            .stdout(ends_with(
+                "Worst failures:\n  edit -> edit (1), expected edits (1)\n  embarass -> embarass (unknown), expected embarrass (unknown)\n",
+            ));
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "eval",
+            &testset,
+            "--learn",
+            &corpus,
+            "--dict-dir",
+            ".",
+            "--worst",
+            "1",
+            "--format",
+            "json",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains(
+                "{\"words\":26,\"correct\":24,\"accuracy\":0.9230769230769231,\"unknown\":1,",
+            ))
+            .stdout(ends_with(
+                "\"failures\":[{\"wrong\":\"edit\",\"right\":\"edits\",\"right_count\":1,\"suggestion\":\"edit\",\"suggestion_count\":1}]}\n",
+            ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_eval__when_testset_has_invalid_line() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("testset.txt"),
+            "spelling: speling\nacess\n",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["eval", "testset.txt", "--dict-dir", "."]);
+        cmd.assert().code(2).stderr(contains(
+            "error: Invalid file testset.txt:2: Expected a line like",
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_diff__shows_changed_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("old.stava"),
+            "the\t3\ncat\t1\nbird\t1\n",
+        )?;
+        fs::write(
+            project_dir.path().join("new.txt"),
+            "the cat the cat the dog",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "diff", "old.stava", "new.txt"]);
+        cmd.assert()
+            .success()
+            .stdout("+ dog 1\n- bird 1\n~ cat 1 -> 2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_merge__combines_word_counts() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("a.stava"), "the\t3\ncat\t1\n")?;
+        fs::write(project_dir.path().join("b.tsv"), "the\t1\ndog\t2\n")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "merge", "a.stava", "b.tsv"]);
+        cmd.assert().success().stdout("the\t4\ndog\t2\ncat\t1\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "merge", "a.stava", "b.tsv", "--strategy", "max"]);
+        cmd.assert().success().stdout("the\t3\ndog\t2\ncat\t1\n");
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args([
+            "dict",
+            "merge",
+            "a.stava",
+            "b.tsv",
+            "--strategy",
+            "weighted",
+        ]);
+        cmd.args(["--weight", "0.75"]);
+        cmd.assert().success().stdout("dog\t2\nthe\t2\ncat\t1\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_forget__removes_words() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("words.stava"),
+            "the\t3\nteh\t1\ncat\t1\n",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "forget", "words.stava", "teh"]);
+        cmd.assert().success().stdout("the\t3\ncat\t1\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_unlearn__subtracts_words_of_files() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join("words.stava"), "the\t3\ncat\t1\n")?;
+        fs::write(project_dir.path().join("old.txt"), "the cat")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "unlearn", "words.stava", "old.txt"]);
+        cmd.assert().success().stdout("the\t2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dict_decay__multiplies_counts() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join("words.stava"),
+            "the\t10\ncat\t4\ndog\t1\n",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.args(["dict", "decay", "words.stava", "--factor", "0.25"]);
+        cmd.assert().success().stdout("the\t3\ncat\t1\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment__splits_and_joins_words() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all("this is the spell checker and the spelling".as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["segment", "thisis", "the", "spel", "ling"]);
+        cmd.arg("--learn").arg(tmp_file.path());
+
+        cmd.assert().success().stdout("this is the spelling\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment__with_json_format() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut tmp_file = tempfile::NamedTempFile::new()?;
+        tmp_file.write_all("the spell checker".as_bytes())?;
+
+        let mut cmd = stava(dir.path())?;
+        cmd.args(["segment", "spellchecker", "--format", "json"]);
+        cmd.arg("--learn").arg(tmp_file.path());
+
+        cmd.assert()
+            .success()
+            .stdout("{\"text\":\"spellchecker\",\"words\":[\"spell\",\"checker\"]}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_exits_with_error__when_word_is_not_ascii() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
+        cmd.arg("spéling");
+
+        cmd.assert().code(2).stdout("").stderr(
+            "error: Invalid word [spéling], only words from the English alphabet are supported\n",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_exits_with_error__when_config_is_invalid() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(
+            project_dir.path().join(".stava.toml"),
+            "max-distance = \"two\"",
+        )?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("speling");
+
+        cmd.assert()
+            .code(2)
+            .stderr(contains("error: Invalid config file"))
+            .stderr(contains("panicked").not());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exits_with_error__when_config_min_confidence_is_out_of_range() -> TestResult {
+        let project_dir = tempfile::tempdir()?;
+        fs::write(project_dir.path().join(".stava.toml"), "min-confidence = 5")?;
+
+        let mut cmd = stava(project_dir.path())?;
+        cmd.arg("speling");
+
+        cmd.assert()
+            .code(2)
+            .stderr("error: Invalid min-confidence: Expected a number between 0 and 1 [5]\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_exits_with_error__when_no_dict_dir() -> TestResult {
+        let dir = tempfile::tempdir()?;
+        let mut cmd = stava(dir.path())?;
+        cmd.env_remove("HOME");
+        cmd.env_remove("XDG_DATA_HOME");
+        cmd.args(["dict", "list"]);
+
+        cmd.assert().code(2).stderr(contains(
+            "error: Could not find a directory for the personal dictionary",
+        ));
+        Ok(())
+    }
 }